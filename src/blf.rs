@@ -0,0 +1,46 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::types::absolute_time::AbsoluteTime;
+
+/// Base timestamp of a Vector `.blf` file, stored as a Windows `SYSTEMTIME`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BlfSystemTime {
+    pub year: u16,
+    pub month: u16,
+    pub day_of_week: u16,
+    pub day: u16,
+    pub hour: u16,
+    pub minute: u16,
+    pub second: u16,
+    pub milliseconds: u16,
+}
+
+/// Converts a BLF `SYSTEMTIME` into an `AbsoluteTime`.
+///
+/// `text` is rendered with the same pattern used by the `.asc` `date` header
+/// (e.g. `"Tue Aug 05 07:23:45.123 pm 2025"`). An all-zero struct (no timestamp
+/// recorded) or an out-of-range field yields `AbsoluteTime::default()`.
+pub fn systemtime_to_absolute_time(st: &BlfSystemTime) -> AbsoluteTime {
+    if *st == BlfSystemTime::default() {
+        return AbsoluteTime::default();
+    }
+
+    let naive_dt: NaiveDateTime =
+        match NaiveDate::from_ymd_opt(i32::from(st.year), u32::from(st.month), u32::from(st.day))
+            .and_then(|date| {
+                date.and_hms_milli_opt(
+                    u32::from(st.hour),
+                    u32::from(st.minute),
+                    u32::from(st.second),
+                    u32::from(st.milliseconds),
+                )
+            }) {
+            Some(v) => v,
+            None => return AbsoluteTime::default(),
+        };
+
+    AbsoluteTime {
+        text: naive_dt.format("%a %b %d %I:%M:%S%.3f %P %Y").to_string(),
+        value: Some(naive_dt),
+    }
+}
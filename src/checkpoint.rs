@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dbc_editor::types::database::SignalKey;
+
+use crate::core::sort;
+use crate::types::errors::{LoadError, SaveError};
+use crate::types::frame::Frame;
+use crate::types::keys::FrameKey;
+use crate::types::log::Log;
+
+/// Magic bytes at the start of every `.tca` checkpoint.
+pub const TCA_MAGIC: &[u8; 3] = b"TCA";
+/// Version of the checkpoint layout, written right after `TCA_MAGIC`.
+pub const TCA_FORMAT_VERSION: u8 = 1;
+/// Version of the appendable checkpoint layout written by `Log::save_checkpoint_incremental`.
+pub const TCA_STREAM_FORMAT_VERSION: u8 = 2;
+
+impl Log {
+    /// Saves the whole `Log` as a binary checkpoint (`.tca` file).
+    ///
+    /// The file starts with `b"TCA"` followed by a format version byte, then the
+    /// `bincode` encoding of the `Log`.
+    pub fn save(&self, path: &str) -> Result<(), SaveError> {
+        if !path.ends_with(".tca") {
+            return Err(SaveError::InvalidExtension {
+                path: path.to_string(),
+            });
+        }
+
+        let payload: Vec<u8> = bincode::serde::encode_to_vec(self, bincode::config::standard())?;
+        let mut bytes: Vec<u8> = Vec::with_capacity(TCA_MAGIC.len() + 1 + payload.len());
+        bytes.extend_from_slice(TCA_MAGIC);
+        bytes.push(TCA_FORMAT_VERSION);
+        bytes.extend_from_slice(&payload);
+
+        fs::write(path, bytes).map_err(|source| SaveError::Write {
+            path: path.to_string(),
+            source,
+        })
+    }
+
+    /// Loads a `Log` previously written by `Log::save`.
+    pub fn load(path: &str) -> Result<Log, LoadError> {
+        if !path.ends_with(".tca") {
+            return Err(LoadError::InvalidExtension {
+                path: path.to_string(),
+            });
+        }
+
+        let bytes: Vec<u8> = fs::read(path).map_err(|source| LoadError::Read {
+            path: path.to_string(),
+            source,
+        })?;
+
+        let header_len: usize = TCA_MAGIC.len() + 1;
+        if bytes.len() < header_len || &bytes[..TCA_MAGIC.len()] != TCA_MAGIC {
+            return Err(LoadError::WrongMagic {
+                path: path.to_string(),
+            });
+        }
+        let version: u8 = bytes[TCA_MAGIC.len()];
+        if version != TCA_FORMAT_VERSION {
+            return Err(LoadError::UnsupportedVersion {
+                path: path.to_string(),
+                version,
+            });
+        }
+
+        let (log, _): (Log, usize) =
+            bincode::serde::decode_from_slice(&bytes[header_len..], bincode::config::standard())?;
+        Ok(log)
+    }
+
+    /// Saves the `Log` as an appendable checkpoint (`.tca` file) and returns the
+    /// number of frames written.
+    ///
+    /// The file starts with `b"TCA"`, `TCA_STREAM_FORMAT_VERSION` and a
+    /// little-endian `u64` stream id, followed by records made of a little-endian
+    /// `u64` length and a `bincode` payload: the first record is the whole `Log`,
+    /// every following one the frames and signal samples added since the previous
+    /// record.
+    ///
+    /// - `new_frames_only = false` overwrites the file with the whole `Log`;
+    /// - `new_frames_only = true` appends what was added since the last checkpoint
+    ///   of this `Log` to `path`. It falls back to a full save if `path` holds
+    ///   another stream, or if the frames or signal samples already saved changed,
+    ///   e.g. after `clear_frames`, `batch_remove_frames`, `apply_dbc_to_channel`,
+    ///   `apply_signal_scaling` or `remove_signal_noise`.
+    pub fn save_checkpoint_incremental(
+        &mut self,
+        path: &str,
+        new_frames_only: bool,
+    ) -> Result<usize, SaveError> {
+        if !path.ends_with(".tca") {
+            return Err(SaveError::InvalidExtension {
+                path: path.to_string(),
+            });
+        }
+        let write_err = |source| SaveError::Write {
+            path: path.to_string(),
+            source,
+        };
+
+        let cursor: Option<CheckpointCursor> = self.checkpoint_cursor.take().filter(|cursor| {
+            new_frames_only
+                && read_stream_id(path) == Some(cursor.stream_id)
+                && cursor.is_prefix_of(self)
+        });
+        let (written, stream_id): (usize, u64) = match cursor {
+            Some(cursor) => {
+                let delta: CheckpointDelta = cursor.delta(self);
+                let written: usize = delta.frames.len();
+                if written > 0 || !delta.signals.is_empty() {
+                    let record: Vec<u8> = encode_record(&delta)?;
+                    let mut file: File = OpenOptions::new()
+                        .append(true)
+                        .open(path)
+                        .map_err(write_err)?;
+                    file.write_all(&record).map_err(write_err)?;
+                }
+                (written, cursor.stream_id)
+            }
+            None => {
+                let stream_id: u64 = new_stream_id();
+                let mut bytes: Vec<u8> = Vec::new();
+                bytes.extend_from_slice(TCA_MAGIC);
+                bytes.push(TCA_STREAM_FORMAT_VERSION);
+                bytes.extend_from_slice(&stream_id.to_le_bytes());
+                bytes.extend_from_slice(&encode_record(&*self)?);
+                fs::write(path, bytes).map_err(write_err)?;
+                (self.frames.len(), stream_id)
+            }
+        };
+
+        self.checkpoint_cursor = Some(CheckpointCursor::new(stream_id, self));
+        Ok(written)
+    }
+
+    /// Loads a `Log` written by `Log::save` or `Log::save_checkpoint_incremental`.
+    ///
+    /// Frames of the incremental records are appended in file order, their signal
+    /// samples appended to the channel databases, and the sorted views are rebuilt
+    /// once at the end. Further incremental saves of the returned `Log` to `path`
+    /// append to the same stream.
+    pub fn load_checkpoint(path: &str) -> Result<Log, LoadError> {
+        if !path.ends_with(".tca") {
+            return Err(LoadError::InvalidExtension {
+                path: path.to_string(),
+            });
+        }
+
+        let bytes: Vec<u8> = fs::read(path).map_err(|source| LoadError::Read {
+            path: path.to_string(),
+            source,
+        })?;
+
+        if bytes.len() < TCA_MAGIC.len() + 1 || &bytes[..TCA_MAGIC.len()] != TCA_MAGIC {
+            return Err(LoadError::WrongMagic {
+                path: path.to_string(),
+            });
+        }
+        match bytes[TCA_MAGIC.len()] {
+            TCA_FORMAT_VERSION => return Log::load(path),
+            TCA_STREAM_FORMAT_VERSION => {}
+            version => {
+                return Err(LoadError::UnsupportedVersion {
+                    path: path.to_string(),
+                    version,
+                });
+            }
+        }
+        let truncated = || LoadError::Truncated {
+            path: path.to_string(),
+        };
+        let (id_bytes, rest) = bytes[TCA_MAGIC.len() + 1..]
+            .split_first_chunk::<8>()
+            .ok_or_else(truncated)?;
+        let stream_id: u64 = u64::from_le_bytes(*id_bytes);
+
+        let mut records = Records { bytes: rest };
+        let mut log: Log = match records.next_record(path)? {
+            Some(record) => {
+                bincode::serde::decode_from_slice::<Log, _>(record, bincode::config::standard())?.0
+            }
+            None => return Err(truncated()),
+        };
+        let mut appended: bool = false;
+        while let Some(record) = records.next_record(path)? {
+            let (delta, _): (CheckpointDelta, usize) =
+                bincode::serde::decode_from_slice(record, bincode::config::standard())?;
+            if delta.first_frame != log.frame_by_file_order.len() {
+                return Err(LoadError::RecordOutOfSequence {
+                    path: path.to_string(),
+                });
+            }
+            for frame in delta.frames {
+                let frame_key: FrameKey = log.frames.insert(frame);
+                log.frame_by_file_order.push(frame_key);
+                appended = true;
+            }
+            for samples in delta.signals {
+                if let Some(signal) = log
+                    .get_mut_database_by_channel(samples.channel)
+                    .and_then(|db| db.get_sig_by_key_mut(samples.sig_key))
+                {
+                    signal.raws.extend(samples.raws);
+                    signal.values.extend(samples.values);
+                }
+            }
+        }
+        if appended {
+            sort::rebuild_views(&mut log);
+        }
+        log.checkpoint_cursor = Some(CheckpointCursor::new(stream_id, &log));
+        Ok(log)
+    }
+}
+
+/// What a `Log` already wrote to its appendable checkpoint, see
+/// `Log::save_checkpoint_incremental`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CheckpointCursor {
+    /// Id written in the header of the stream.
+    stream_id: u64,
+    /// Number of frames saved, in file order.
+    frames_saved: usize,
+    /// Last frame saved, to detect a `frame_by_file_order` rebuilt since.
+    last_frame: Option<FrameKey>,
+    /// `(raws, values)` samples saved of every decoded signal.
+    signal_lens: HashMap<(u8, SignalKey), (usize, usize)>,
+}
+
+impl CheckpointCursor {
+    fn new(stream_id: u64, log: &Log) -> Self {
+        let signal_lens: HashMap<(u8, SignalKey), (usize, usize)> = log
+            .observed_signals()
+            .into_iter()
+            .filter_map(|(channel, sig_key)| {
+                let signal = log
+                    .get_database_by_channel(channel)?
+                    .get_sig_by_key(sig_key)?;
+                Some(((channel, sig_key), (signal.raws.len(), signal.values.len())))
+            })
+            .collect();
+        Self {
+            stream_id,
+            frames_saved: log.frame_by_file_order.len(),
+            last_frame: log.frame_by_file_order.last().copied(),
+            signal_lens,
+        }
+    }
+
+    /// Return true if what was saved is still the start of the frames and of the
+    /// signal samples of `log`.
+    fn is_prefix_of(&self, log: &Log) -> bool {
+        let frames_kept: bool = match self.frames_saved.checked_sub(1) {
+            Some(last) => {
+                log.frame_by_file_order.get(last).copied() == self.last_frame
+                    && self
+                        .last_frame
+                        .is_some_and(|key| log.frames.contains_key(key))
+            }
+            None => true,
+        };
+        frames_kept
+            && self
+                .signal_lens
+                .iter()
+                .all(|(&(channel, sig_key), &(raws, values))| {
+                    log.get_database_by_channel(channel)
+                        .and_then(|db| db.get_sig_by_key(sig_key))
+                        .is_some_and(|signal| {
+                            signal.raws.len() >= raws && signal.values.len() >= values
+                        })
+                })
+    }
+
+    /// Frames and signal samples of `log` added since the cursor was taken.
+    fn delta(&self, log: &Log) -> CheckpointDelta {
+        let frames: Vec<Frame> = log.frame_by_file_order[self.frames_saved..]
+            .iter()
+            .filter_map(|key| log.frames.get(*key))
+            .cloned()
+            .collect();
+        let mut signals: Vec<SignalSamples> = Vec::new();
+        for (channel, sig_key) in log.observed_signals() {
+            let Some(signal) = log
+                .get_database_by_channel(channel)
+                .and_then(|db| db.get_sig_by_key(sig_key))
+            else {
+                continue;
+            };
+            let (raws, values) = self
+                .signal_lens
+                .get(&(channel, sig_key))
+                .copied()
+                .unwrap_or((0, 0));
+            if signal.raws.len() > raws || signal.values.len() > values {
+                signals.push(SignalSamples {
+                    channel,
+                    sig_key,
+                    raws: signal.raws[raws..].to_vec(),
+                    values: signal.values[values..].to_vec(),
+                });
+            }
+        }
+        CheckpointDelta {
+            first_frame: self.frames_saved,
+            frames,
+            signals,
+        }
+    }
+}
+
+/// Incremental record of an appendable checkpoint.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CheckpointDelta {
+    /// Frames of the stream before this record.
+    first_frame: usize,
+    frames: Vec<Frame>,
+    signals: Vec<SignalSamples>,
+}
+
+/// Signal samples appended to the database of `channel` by a `CheckpointDelta`.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SignalSamples {
+    channel: u8,
+    sig_key: SignalKey,
+    raws: Vec<(f64, i64)>,
+    values: Vec<(f64, f64)>,
+}
+
+/// Stream id of `path` if it starts with the header of an appendable checkpoint.
+fn read_stream_id(path: &str) -> Option<u64> {
+    let mut header: [u8; 12] = [0; 12];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .ok()?;
+    if &header[..3] != TCA_MAGIC || header[3] != TCA_STREAM_FORMAT_VERSION {
+        return None;
+    }
+    header[4..].try_into().ok().map(u64::from_le_bytes)
+}
+
+/// Id of a new appendable checkpoint, unique enough to tell two streams apart.
+fn new_stream_id() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos: u64 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+    nanos ^ (u64::from(std::process::id()) << 32) ^ COUNTER.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Encode `value` as a length-prefixed record.
+fn encode_record<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, SaveError> {
+    let payload: Vec<u8> = bincode::serde::encode_to_vec(value, bincode::config::standard())?;
+    let mut record: Vec<u8> = Vec::with_capacity(8 + payload.len());
+    record.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    record.extend_from_slice(&payload);
+    Ok(record)
+}
+
+/// Cursor over the length-prefixed records of an appendable checkpoint.
+struct Records<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Records<'a> {
+    fn next_record(&mut self, path: &str) -> Result<Option<&'a [u8]>, LoadError> {
+        if self.bytes.is_empty() {
+            return Ok(None);
+        }
+        let truncated = || LoadError::Truncated {
+            path: path.to_string(),
+        };
+        let (len_bytes, rest) = self.bytes.split_first_chunk::<8>().ok_or_else(truncated)?;
+        let len: usize =
+            usize::try_from(u64::from_le_bytes(*len_bytes)).map_err(|_| truncated())?;
+        if rest.len() < len {
+            return Err(truncated());
+        }
+        let (record, rest) = rest.split_at(len);
+        self.bytes = rest;
+        Ok(Some(record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::frame::{FrameBuilder, FrameType};
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!(
+                "trace_analyzer_{}_{}.tca",
+                name,
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn frames_in_file_order(log: &Log) -> Vec<Frame> {
+        log.frame_by_file_order
+            .iter()
+            .map(|key| log.frames[*key].clone())
+            .collect()
+    }
+
+    #[test]
+    fn save_load_round_trip() {
+        let mut log: Log = Log::from_frames(vec![
+            FrameBuilder::new()
+                .timestamp(0.1)
+                .channel(1)
+                .id(0x123)
+                .data("01 02 03")
+                .build(),
+            FrameBuilder::new()
+                .timestamp(0.2)
+                .channel(2)
+                .ftype(FrameType::ErrorFrame)
+                .build(),
+        ]);
+        log.comments.push((3, "note".to_string()));
+
+        let path: String = temp_path("round_trip");
+        log.save(&path).unwrap();
+        let loaded: Result<Log, LoadError> = Log::load(&path);
+        fs::remove_file(&path).ok();
+        let loaded: Log = loaded.unwrap();
+
+        assert_eq!(frames_in_file_order(&loaded), frames_in_file_order(&log));
+        assert_eq!(loaded.frame_by_timestamp, log.frame_by_timestamp);
+        assert_eq!(loaded.comments, log.comments);
+        assert!(loaded.channel_map.contains_key(&1) && loaded.channel_map.contains_key(&2));
+    }
+
+    fn can_frame(timestamp: f64) -> Frame {
+        FrameBuilder::new()
+            .timestamp(timestamp)
+            .channel(1)
+            .id(0x100)
+            .data("AA BB")
+            .build()
+    }
+
+    fn append_frame(log: &mut Log, frame: Frame) {
+        let key: FrameKey = log.frames.insert(frame);
+        log.frame_by_file_order.push(key);
+        sort::rebuild_views(log);
+    }
+
+    #[test]
+    fn incremental_checkpoint_round_trip() {
+        let path: String = temp_path("incremental");
+        let mut log: Log = Log::from_frames(vec![can_frame(0.1), can_frame(0.2)]);
+        assert_eq!(log.save_checkpoint_incremental(&path, true).unwrap(), 2);
+
+        append_frame(&mut log, can_frame(0.3));
+        assert_eq!(log.save_checkpoint_incremental(&path, true).unwrap(), 1);
+        let mut loaded: Log = Log::load_checkpoint(&path).unwrap();
+        assert_eq!(frames_in_file_order(&loaded), frames_in_file_order(&log));
+
+        // the loaded log keeps appending to the same stream
+        append_frame(&mut loaded, can_frame(0.4));
+        assert_eq!(loaded.save_checkpoint_incremental(&path, true).unwrap(), 1);
+        assert_eq!(Log::load_checkpoint(&path).unwrap().frames.len(), 4);
+
+        // another log does not append to a stream it did not write
+        let mut other: Log = Log::from_frames(vec![can_frame(1.0)]);
+        other.checkpoint_cursor = log.checkpoint_cursor.clone();
+        assert_eq!(other.save_checkpoint_incremental(&path, true).unwrap(), 1);
+        let result: Result<Log, LoadError> = Log::load_checkpoint(&path);
+        fs::remove_file(&path).ok();
+        assert_eq!(frames_in_file_order(&result.unwrap()), vec![can_frame(1.0)]);
+    }
+
+    #[test]
+    fn incremental_checkpoint_after_clear_is_full() {
+        let path: String = temp_path("incremental_clear");
+        let mut log: Log = Log::from_frames(vec![can_frame(0.1), can_frame(0.2)]);
+        log.save_checkpoint_incremental(&path, true).unwrap();
+
+        log.clear_frames();
+        append_frame(&mut log, can_frame(0.5));
+        assert_eq!(log.save_checkpoint_incremental(&path, true).unwrap(), 1);
+        let result: Result<Log, LoadError> = Log::load_checkpoint(&path);
+        fs::remove_file(&path).ok();
+        assert_eq!(frames_in_file_order(&result.unwrap()), vec![can_frame(0.5)]);
+    }
+}
@@ -12,7 +12,12 @@ pub(crate) fn from_line(line: &str) -> Option<AbsoluteTime> {
     }
 
     // rebuild data
-    let date_str: String = parts.collect::<Vec<_>>().join(" ");
+    from_date_text(&parts.collect::<Vec<_>>().join(" "))
+}
+
+/// Parse the date text following `date` or `Begin Triggerblock`.
+pub(crate) fn from_date_text(text: &str) -> Option<AbsoluteTime> {
+    let date_str: String = text.split_ascii_whitespace().collect::<Vec<_>>().join(" ");
 
     // Chrono parsing pattern
     let fmt: &str = "%a %b %d %I:%M:%S%.3f %P %Y";
@@ -5,12 +5,43 @@ use smallvec::SmallVec;
 use crate::types::frame::{Direction, Frame, FrameType};
 use crate::types::keys::FrameKey;
 use crate::types::log::{ChannelType, Log};
+use crate::types::parse_options::ParseOptions;
+use crate::types::statistics::{ErrorCountEntry, FdBusStatEntry};
+use crate::types::trigger::Trigger;
 
 const MAX_CAN_PAYLOAD: usize = 64;
 
+/// Keywords of header and marker lines, never reported as skipped.
+const IGNORED_KEYWORDS: [&str; 5] = ["base", "internal", "no", "Start", "SIMULATION"];
+
+/// Progress counters of a `LineParser`, see `LineParser::state`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LineParserState {
+    /// Lines passed to `parse`, i.e. the 1-based number of the last line.
+    pub lines_processed: usize,
+    /// Frames added to the `Log`, error frames included.
+    pub frames_accepted: usize,
+    /// Frame lines dropped because malformed or on a channel missing from
+    /// `Log::channel_map`. Frames removed by the channel filter are not counted.
+    pub frames_rejected: usize,
+    /// Timestamp of the last line that started with one.
+    pub last_timestamp: Option<f64>,
+}
+
 pub struct LineParser {
     data_buf: String,
     payload_buf: SmallVec<[u8; MAX_CAN_PAYLOAD]>,
+    state: LineParserState,
+    /// Count unsupported keywords in `Log::skipped_tokens`.
+    collect_skipped_tokens: bool,
+    /// Channels whose frames are kept, `None` keeps every channel.
+    channel_filter: Option<Vec<u8>>,
+    /// Between `DISTURBANCE begin` and `DISTURBANCE end`.
+    in_disturbance_block: bool,
+    skip_disturbance_blocks: bool,
+    tag_disturbed_frames: bool,
+    /// Start of the current trigger block, see `set_time_offset`.
+    time_offset: f64,
 }
 
 impl LineParser {
@@ -18,19 +49,67 @@ impl LineParser {
         Self {
             data_buf: String::with_capacity(24),
             payload_buf: SmallVec::new(),
+            state: LineParserState::default(),
+            collect_skipped_tokens: false,
+            channel_filter: None,
+            in_disturbance_block: false,
+            skip_disturbance_blocks: true,
+            tag_disturbed_frames: false,
+            time_offset: 0.0,
+        }
+    }
+
+    pub fn with_options(options: &ParseOptions) -> Self {
+        Self {
+            collect_skipped_tokens: options.collect_skipped_tokens,
+            channel_filter: options.channel_filter.clone(),
+            skip_disturbance_blocks: options.skip_disturbance_blocks,
+            tag_disturbed_frames: options.tag_disturbed_frames,
+            ..Self::new()
         }
     }
 
+    /// Counters of the lines parsed so far.
+    pub fn state(&self) -> &LineParserState {
+        &self.state
+    }
+
+    /// Count a line handled by the caller without parsing it, so the line numbers
+    /// of the following comments stay aligned with the file.
+    pub fn skip_line(&mut self) {
+        self.state.lines_processed += 1;
+    }
+
+    /// Seconds added to the line timestamps of the following signal samples and
+    /// triggers, i.e. `TriggerBlock::start_timestamp` of the block being parsed.
+    /// `Frame::timestamp` keeps the timestamp written in the line.
+    pub fn set_time_offset(&mut self, offset: f64) {
+        self.time_offset = offset;
+    }
+
+    /// Zero the counters of `state`, line numbers of the following comments restart at 1.
+    pub fn reset_state(&mut self) {
+        self.state = LineParserState::default();
+    }
+
     // Example:
     // 0.016728 1 17334410x Rx d 8 3E 42 03 00 39 00 03 01
     // 0.016728 1 17334410x Rx Name ECU d 8 3E 42 03 00 39 00 03 01
+    // 0.016728 CANFD 1 17334410x Rx BrsOn EsiValid d 12 3E 42 03 00 39 00 03 01 00 00 00 00
+    // 2.501046 1 ErrorFrame
     pub fn parse(&mut self, line: &str, log: &mut Log) {
+        self.state.lines_processed += 1;
+
+        // User annotations: "// some text"
+        if let Some(comment) = line.trim_start().strip_prefix("//") {
+            log.comments
+                .push((self.state.lines_processed, comment.trim().to_string()));
+            return;
+        }
+
         // split line by whitespaces (ASCII only, faster than Unicode-aware split)
         let mut it = line.split_ascii_whitespace();
 
-        // Build the frame
-        let mut frame: Frame = Frame::default();
-
         // Timestamp
         let ts_tok: &str = match it.next() {
             Some(v) => v,
@@ -38,8 +117,14 @@ impl LineParser {
         };
         let timestamp: f64 = match ts_tok.parse() {
             Ok(v) => v,
-            Err(_) => return,
+            Err(_) => {
+                // Keyword lines without a leading timestamp, e.g. "CANFD ..."
+                let last_timestamp: f64 = self.state.last_timestamp.unwrap_or(0.0);
+                self.parse_keyword_line(ts_tok, &mut it, last_timestamp, log);
+                return;
+            }
         };
+        self.state.last_timestamp = Some(timestamp);
 
         // Channel
         let ch_tok: &str = match it.next() {
@@ -48,17 +133,103 @@ impl LineParser {
         };
         let channel: u8 = match ch_tok.parse::<u8>() {
             Ok(v) => v,
-            Err(_) => return,
+            Err(_) => {
+                self.parse_keyword_line(ch_tok, &mut it, timestamp, log);
+                return;
+            }
         };
 
+        self.parse_frame(&mut it, timestamp, channel, log);
+    }
+
+    /// Dispatches the lines whose first token (after the optional timestamp) is a keyword.
+    fn parse_keyword_line<'a>(
+        &mut self,
+        keyword: &str,
+        it: &mut impl Iterator<Item = &'a str>,
+        timestamp: f64,
+        log: &mut Log,
+    ) {
+        match keyword {
+            "CANFD" => self.parse_canfd_line(it, timestamp, log),
+            "CANFD_BUSSTATISTICS" => parse_fd_bus_statistics(it, timestamp, log),
+            "ERRORCOUNT" => parse_error_count(it, timestamp, log),
+            "DISTURBANCE" => match it.next() {
+                Some(tok) if tok.eq_ignore_ascii_case("begin") => self.in_disturbance_block = true,
+                Some(tok) if tok.eq_ignore_ascii_case("end") => self.in_disturbance_block = false,
+                _ => self.record_skipped(keyword, log),
+            },
+            _ if keyword.eq_ignore_ascii_case("TRIGGER") => {
+                parse_trigger(it, timestamp, self.time_offset, log)
+            }
+            _ if IGNORED_KEYWORDS.contains(&keyword) => {}
+            _ => self.record_skipped(keyword, log),
+        }
+    }
+
+    /// Counts one occurrence of an unsupported token in `log.skipped_tokens`.
+    fn record_skipped(&self, token: &str, log: &mut Log) {
+        if !self.collect_skipped_tokens {
+            return;
+        }
+        match log.skipped_tokens.iter_mut().find(|(tok, _)| tok == token) {
+            Some((_, count)) => *count += 1,
+            None => log.skipped_tokens.push((token.to_string(), 1)),
+        }
+    }
+
+    /// Parses the tokens following the `CANFD` keyword:
+    /// `<channel> <id> <direction> [BrsOn|BrsOff] [EsiError|EsiValid] d <len> <payload>`.
+    fn parse_canfd_line<'a>(
+        &mut self,
+        it: &mut impl Iterator<Item = &'a str>,
+        timestamp: f64,
+        log: &mut Log,
+    ) {
+        let channel: u8 = match it.next().and_then(|tok| tok.parse::<u8>().ok()) {
+            Some(v) => v,
+            None => {
+                self.state.frames_rejected += 1;
+                return;
+            }
+        };
+        self.parse_frame(it, timestamp, channel, log);
+    }
+
+    /// Parses the tokens following the channel: `<id> <direction> ... d <len> <payload>`.
+    fn parse_frame<'a>(
+        &mut self,
+        it: &mut impl Iterator<Item = &'a str>,
+        timestamp: f64,
+        channel: u8,
+        log: &mut Log,
+    ) {
+        // Skip filtered out channels before any allocation
+        if let Some(filter) = &self.channel_filter
+            && !filter.contains(&channel)
+        {
+            return;
+        }
+        let disturbed: bool = self.in_disturbance_block;
+        if disturbed && self.skip_disturbance_blocks && !self.tag_disturbed_frames {
+            return;
+        }
+
+        // Build the frame
+        let mut frame: Frame = Frame::default();
+
         frame.timestamp = timestamp;
         frame.channel = channel;
+        frame.is_disturbed = disturbed && self.tag_disturbed_frames;
         match log.channel_map.get(&channel) {
             Some(ch_info) => match ch_info.tipo {
                 ChannelType::Can => frame.ftype = FrameType::Can,
                 ChannelType::Ethernet => frame.ftype = FrameType::Eth,
             },
-            None => return,
+            None => {
+                self.state.frames_rejected += 1;
+                return;
+            }
         }
 
         // -------- Can Frame parsing ----------- //
@@ -68,15 +239,26 @@ impl LineParser {
                 Some(v) => v,
                 None => {
                     frame.ftype = FrameType::ErrorFrame;
-                    let frame_key: FrameKey = log.frames.insert(frame);
-                    log.frame_by_file_order.push(frame_key);
+                    self.push_frame(frame, log);
                     return;
                 }
             };
+            // Error frame: "<timestamp> <channel> ErrorFrame", no id nor payload
+            if id_tok == "ErrorFrame" {
+                frame.ftype = FrameType::ErrorFrame;
+                frame.absolute_time = frame_absolute_time(log.absolute_time.value, timestamp);
+                self.push_frame(frame, log);
+                return;
+            }
             // Message Id e Id_Hex
             let id: u32 = match parse_id_u32(id_tok) {
                 Some(v) => v,
-                None => return,
+                None => {
+                    // e.g. "Statistic:"
+                    self.record_skipped(id_tok, log);
+                    self.state.frames_rejected += 1;
+                    return;
+                }
             };
 
             frame.id = id;
@@ -89,26 +271,50 @@ impl LineParser {
                     "Rx" => frame.direction = Direction::Rx,
                     _ => {
                         frame.ftype = FrameType::ErrorFrame;
-                        let frame_key: FrameKey = log.frames.insert(frame);
-                        log.frame_by_file_order.push(frame_key);
+                        self.push_frame(frame, log);
                         return;
                     }
                 },
                 None => {
                     frame.ftype = FrameType::ErrorFrame;
-                    let frame_key: FrameKey = log.frames.insert(frame);
-                    log.frame_by_file_order.push(frame_key);
+                    self.push_frame(frame, log);
                     return;
                 }
             };
 
-            // Scan forward to 'd' or 'D', then read byte length and payload tokens
+            // Scan forward to 'd' or 'D', then read byte length and payload tokens.
+            // CAN-FD flags may appear in between.
             let mut after_d: Option<&str> = None;
+            let mut remote: bool = false;
             while let Some(tok) = it.next() {
-                if tok == "d" || tok == "D" {
-                    after_d = it.next(); // next is byte length
-                    break;
+                match tok {
+                    "d" | "D" => {
+                        after_d = it.next(); // next is byte length
+                        break;
+                    }
+                    "r" | "R" => {
+                        remote = true;
+                        after_d = it.next(); // optional byte length
+                        break;
+                    }
+                    "BrsOn" => frame.brs_enabled = true,
+                    "BrsOff" => frame.brs_enabled = false,
+                    "EsiError" => frame.esi_error = true,
+                    "EsiValid" => frame.esi_error = false,
+                    _ => {}
+                }
+            }
+
+            // Remote frame: DLC without payload, nothing to decode
+            if remote {
+                frame.ftype = FrameType::Remote;
+                frame.byte_length = after_d.and_then(|s| s.parse().ok()).unwrap_or(0);
+                frame.absolute_time = frame_absolute_time(log.absolute_time.value, timestamp);
+                if let Some(dbc) = log.get_database_by_channel(channel) {
+                    resolve_remote_frame(dbc, &mut frame);
                 }
+                self.push_frame(frame, log);
+                return;
             }
 
             // Byte Length
@@ -116,8 +322,7 @@ impl LineParser {
                 Some(v) => v,
                 None => {
                     frame.ftype = FrameType::ErrorFrame;
-                    let frame_key: FrameKey = log.frames.insert(frame);
-                    log.frame_by_file_order.push(frame_key);
+                    self.push_frame(frame, log);
                     return;
                 }
             };
@@ -139,7 +344,11 @@ impl LineParser {
             for i in 0..payload_len {
                 let tok = match it.next() {
                     Some(v) => v,
-                    None => return, // malformed: not enough data bytes
+                    None => {
+                        // malformed: not enough data bytes
+                        self.state.frames_rejected += 1;
+                        return;
+                    }
                 };
                 if i != 0 {
                     self.data_buf.push(' ');
@@ -148,7 +357,10 @@ impl LineParser {
 
                 let byte = match u8::from_str_radix(tok, 16) {
                     Ok(v) => v,
-                    Err(_) => return,
+                    Err(_) => {
+                        self.state.frames_rejected += 1;
+                        return;
+                    }
                 };
                 self.payload_buf.push(byte);
             }
@@ -156,48 +368,93 @@ impl LineParser {
             std::mem::swap(&mut frame.data, &mut self.data_buf);
 
             // absolute time of the single CanFrame
-            frame.absolute_time = if let Some(start_time) = log.absolute_time.value {
-                let delta_ms: i64 = (timestamp * 1000.0).round() as i64;
-                let abs_time_value: NaiveDateTime = start_time + Duration::milliseconds(delta_ms);
-                format_datetime_ymdhms_millis(abs_time_value)
-            } else {
-                seconds_to_hms_string(timestamp)
-            };
+            frame.absolute_time = frame_absolute_time(log.absolute_time.value, timestamp);
 
             // If a DBC is available for this channel, try to decode
-            if let Some(dbc) = log.get_database_by_channel(channel)
-                && let Some(msg_key) = resolve_msg_key_for_id(dbc, id)
-                && let Some(msg) = dbc.get_message_by_key(msg_key)
-            {
-                frame.msg_key = msg_key;
-                if let Some(&node_key) = msg.sender_nodes.first() {
-                    frame.tx_node_key = node_key;
-                }
-                frame.sig_keys = msg.signals.clone();
-            }
-
             if let Some(dbc) = log.get_mut_database_by_channel(channel) {
-                let payload_bytes: &[u8] = &self.payload_buf;
-                for &sig_key in frame.sig_keys.iter() {
-                    if let Some(signal) = dbc.get_sig_by_key_mut(sig_key) {
-                        let raw: i64 = signal.extract_raw_i64(&payload_bytes);
-                        let value: f64 = (raw as f64) * signal.factor + signal.offset;
-
-                        // Append a point to the corresponding SignalDBC time series
-                        signal.raws.push((timestamp, raw));
-                        signal.values.push((timestamp, value));
-                    }
-                }
+                let sample_time: f64 = timestamp + self.time_offset;
+                decode_with_database(dbc, &mut frame, &self.payload_buf, sample_time);
             };
 
             // Inserisci il frame nella lista una volta terminata la decodifica
-            let frame_key: FrameKey = log.frames.insert(frame);
-            log.frame_by_file_order.push(frame_key);
+            self.push_frame(frame, log);
         } // if frame.ftype == FrameType::Can
     }
+
+    /// Appends `frame` to `log.frames` and `frame_by_file_order`.
+    fn push_frame(&mut self, frame: Frame, log: &mut Log) {
+        let frame_key: FrameKey = log.frames.insert(frame);
+        log.frame_by_file_order.push(frame_key);
+        self.state.frames_accepted += 1;
+    }
+}
+
+/// `CANFD_BUSSTATISTICS <channel> <frame_rate> <bus_load>[%]`
+fn parse_fd_bus_statistics<'a>(
+    it: &mut impl Iterator<Item = &'a str>,
+    timestamp: f64,
+    log: &mut Log,
+) {
+    let channel: u8 = match it.next().and_then(|tok| tok.parse().ok()) {
+        Some(v) => v,
+        None => return,
+    };
+    let mut numbers = it.filter_map(|tok| tok.trim_end_matches('%').parse::<f64>().ok());
+    let frame_rate: f64 = numbers.next().unwrap_or(0.0);
+    let bus_load: f64 = numbers.next().unwrap_or(0.0);
+    log.fd_bus_statistics.push(FdBusStatEntry {
+        timestamp,
+        channel,
+        frame_rate,
+        bus_load,
+    });
 }
 
-fn seconds_to_hms_string(seconds: f64) -> String {
+/// `TRIGGER "<name>" [at <timestamp>]`, without `at` the line timestamp is used.
+/// `time_offset` is added to either, see `LineParser::set_time_offset`.
+fn parse_trigger<'a>(
+    it: &mut impl Iterator<Item = &'a str>,
+    timestamp: f64,
+    time_offset: f64,
+    log: &mut Log,
+) {
+    let rest: String = it.collect::<Vec<&str>>().join(" ");
+    let (name, after) = match rest.strip_prefix('"').and_then(|r| r.split_once('"')) {
+        Some((name, after)) => (name.to_string(), after),
+        None => match rest.find(" at ") {
+            Some(pos) => (rest[..pos].to_string(), &rest[pos..]),
+            None => (rest.clone(), ""),
+        },
+    };
+    let mut tokens = after.split_ascii_whitespace();
+    let timestamp: f64 = match (tokens.next(), tokens.next()) {
+        (Some(at), Some(ts)) if at.eq_ignore_ascii_case("at") => ts.parse().unwrap_or(timestamp),
+        _ => timestamp,
+    };
+    let timestamp: f64 = timestamp + time_offset;
+
+    // keep `log.triggers` sorted for `Log::triggers_in_range`
+    let index: usize = log.triggers.partition_point(|t| t.timestamp <= timestamp);
+    log.triggers.insert(index, Trigger { timestamp, name });
+}
+
+/// `ERRORCOUNT <channel> <error_type> [<count>]`
+fn parse_error_count<'a>(it: &mut impl Iterator<Item = &'a str>, timestamp: f64, log: &mut Log) {
+    let channel: u8 = match it.next().and_then(|tok| tok.parse().ok()) {
+        Some(v) => v,
+        None => return,
+    };
+    let error_type: String = it.next().unwrap_or_default().to_string();
+    let count: u32 = it.next().and_then(|tok| tok.parse().ok()).unwrap_or(0);
+    log.error_counts.push(ErrorCountEntry {
+        timestamp,
+        channel,
+        error_type,
+        count,
+    });
+}
+
+pub(crate) fn seconds_to_hms_string(seconds: f64) -> String {
     let total_millis: u32 = (seconds * 1000.0).round() as u32;
 
     let hours: u32 = total_millis / 3_600_000;
@@ -212,7 +469,7 @@ fn seconds_to_hms_string(seconds: f64) -> String {
 }
 
 // Fast formatter: YYYY-MM-DD HH:MM:SS.mmm
-fn format_datetime_ymdhms_millis(dt: NaiveDateTime) -> String {
+pub(crate) fn format_datetime_ymdhms_millis(dt: NaiveDateTime) -> String {
     let year: i32 = dt.year();
     let month: u32 = dt.month();
     let day: u32 = dt.day();
@@ -257,7 +514,7 @@ fn push_3(buf: &mut String, v: u32) {
 }
 
 /// Normalize an ASC id token like "17334410x" or "12AB" to "0x17334410" / "0x12AB".
-fn parse_id_u32(id_token: &str) -> Option<u32> {
+pub(crate) fn parse_id_u32(id_token: &str) -> Option<u32> {
     // strip trailing x/X (extended id marker)
     let s: &str = id_token.trim_end_matches(['x', 'X']);
     let s: &str = if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
@@ -276,7 +533,7 @@ fn parse_id_u32(id_token: &str) -> Option<u32> {
 const CAN_STD_MAX_ID: u32 = 0x7FF;
 const CAN_EFF_FLAG: u32 = 0x8000_0000;
 
-fn resolve_msg_key_for_id(dbc: &DatabaseDBC, id: u32) -> Option<MessageKey> {
+pub(crate) fn resolve_msg_key_for_id(dbc: &DatabaseDBC, id: u32) -> Option<MessageKey> {
     dbc.get_msg_key_by_id(id).or_else(|| {
         if id > CAN_STD_MAX_ID {
             dbc.get_msg_key_by_id(id | CAN_EFF_FLAG)
@@ -285,3 +542,60 @@ fn resolve_msg_key_for_id(dbc: &DatabaseDBC, id: u32) -> Option<MessageKey> {
         }
     })
 }
+
+/// Text of `Frame::absolute_time`: the date and time `timestamp` seconds after
+/// `start_time`, or the relative time when the log has no absolute start.
+pub(crate) fn frame_absolute_time(start_time: Option<NaiveDateTime>, timestamp: f64) -> String {
+    match start_time {
+        Some(start_time) => {
+            let delta_ms: i64 = (timestamp * 1000.0).round() as i64;
+            format_datetime_ymdhms_millis(start_time + Duration::milliseconds(delta_ms))
+        }
+        None => seconds_to_hms_string(timestamp),
+    }
+}
+
+/// Resolve the DBC message and first sender of a remote `frame`, which carries no
+/// signals.
+pub(crate) fn resolve_remote_frame(dbc: &DatabaseDBC, frame: &mut Frame) {
+    if let Some(msg_key) = resolve_msg_key_for_id(dbc, frame.id) {
+        frame.msg_key = msg_key;
+        if let Some(&node_key) = dbc
+            .get_message_by_key(msg_key)
+            .and_then(|msg| msg.sender_nodes.first())
+        {
+            frame.tx_node_key = node_key;
+        }
+    }
+}
+
+/// Resolve the DBC message of a CAN `frame` (message, first sender and signals)
+/// and append the decoded signal values to the database time series at
+/// `sample_time`, the block-adjusted timestamp of the frame.
+pub(crate) fn decode_with_database(
+    dbc: &mut DatabaseDBC,
+    frame: &mut Frame,
+    payload: &[u8],
+    sample_time: f64,
+) {
+    if let Some(msg_key) = resolve_msg_key_for_id(dbc, frame.id)
+        && let Some(msg) = dbc.get_message_by_key(msg_key)
+    {
+        frame.msg_key = msg_key;
+        if let Some(&node_key) = msg.sender_nodes.first() {
+            frame.tx_node_key = node_key;
+        }
+        frame.sig_keys = msg.signals.clone();
+    }
+
+    for &sig_key in frame.sig_keys.iter() {
+        if let Some(signal) = dbc.get_sig_by_key_mut(sig_key) {
+            let raw: i64 = signal.extract_raw_i64(payload);
+            let value: f64 = (raw as f64) * signal.factor + signal.offset;
+
+            // Append a point to the corresponding SignalDBC time series
+            signal.raws.push((sample_time, raw));
+            signal.values.push((sample_time, value));
+        }
+    }
+}
@@ -1,2 +1,3 @@
 pub mod abs_time;
 pub mod line;
+pub(crate) mod sort;
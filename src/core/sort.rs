@@ -0,0 +1,801 @@
+use std::collections::HashMap;
+
+use ordered_float::OrderedFloat;
+use slotmap::SlotMap;
+
+use crate::types::frame::{Direction, Frame, FrameType};
+use crate::types::keys::FrameKey;
+use crate::types::log::{CYCLIC_MAX_GAP_CV, CYCLIC_MIN_REPEAT_COUNT, ChannelInfo, Log};
+use crate::types::trigger::{self, TriggerBlock};
+
+/// Rebuilds every sorted view of the `Log` starting from `frame_by_file_order`.
+pub(crate) fn rebuild_views(log: &mut Log) {
+    log.invalidate_caches();
+    rebuild(log, false);
+}
+
+/// Same as `rebuild_views`, but the views are sorted concurrently and every
+/// single sort runs on the rayon thread pool.
+#[cfg(feature = "rayon")]
+pub(crate) fn rebuild_views_parallel(log: &mut Log) {
+    log.invalidate_caches();
+    rebuild(log, true);
+}
+
+/// Rebuilds only `frame_by_can_cyclic_only` and `frame_by_can_event_driven_only`
+/// with custom classification thresholds.
+pub(crate) fn rebuild_cyclic_views(log: &mut Log, min_repeat_count: usize, max_gap_cv: f64) {
+    let Log {
+        frames,
+        channel_map,
+        trigger_blocks,
+        frame_by_file_order,
+        frame_by_can_cyclic_only,
+        frame_by_can_event_driven_only,
+        ..
+    } = log;
+
+    let base_keys: &[FrameKey] = frame_by_file_order.as_slice();
+    let sorter: ViewSorter = ViewSorter::new(frames, channel_map, trigger_blocks, base_keys, false);
+    let (cyclic_keys, event_keys) = sorter.cyclic_split(base_keys, min_repeat_count, max_gap_cv);
+    for (target, source) in [
+        (frame_by_can_cyclic_only, cyclic_keys),
+        (frame_by_can_event_driven_only, event_keys),
+    ] {
+        refill(target, &source);
+        sorter.sort(SortKind::Timestamp, target);
+    }
+}
+
+/// Return a description of every sorted view that is not ordered by its sort key.
+#[cfg(any(debug_assertions, feature = "integrity"))]
+pub(crate) fn unsorted_views(log: &Log) -> Vec<String> {
+    let sorter: ViewSorter = ViewSorter::new(
+        &log.frames,
+        &log.channel_map,
+        &log.trigger_blocks,
+        &log.frame_by_file_order,
+        false,
+    );
+    let views: Vec<(&str, &[FrameKey], SortKind)> = vec![
+        (
+            "frame_by_timestamp",
+            &log.frame_by_timestamp,
+            SortKind::Timestamp,
+        ),
+        (
+            "id_chn_by_timestamp",
+            &log.id_chn_by_timestamp,
+            SortKind::Timestamp,
+        ),
+        (
+            "frame_by_block_timestamp",
+            &log.frame_by_block_timestamp,
+            SortKind::BlockTimestamp,
+        ),
+        ("frame_by_channel", &log.frame_by_channel, SortKind::Channel),
+        (
+            "id_chn_by_channel",
+            &log.id_chn_by_channel,
+            SortKind::Channel,
+        ),
+        (
+            "frame_by_direction",
+            &log.frame_by_direction,
+            SortKind::Direction,
+        ),
+        (
+            "id_chn_by_direction",
+            &log.id_chn_by_direction,
+            SortKind::Direction,
+        ),
+        (
+            "frame_by_can_msg_name",
+            &log.frame_by_can_msg_name,
+            SortKind::CanMsgName,
+        ),
+        (
+            "frame_by_can_signal_name",
+            &log.frame_by_can_signal_name,
+            SortKind::CanSignalName,
+        ),
+        (
+            "id_chn_by_can_msg_name",
+            &log.id_chn_by_can_msg_name,
+            SortKind::CanMsgName,
+        ),
+        (
+            "frame_by_can_msg_id",
+            &log.frame_by_can_msg_id,
+            SortKind::CanMsgId,
+        ),
+        (
+            "id_chn_by_can_msg_id",
+            &log.id_chn_by_can_msg_id,
+            SortKind::CanMsgId,
+        ),
+        ("frame_by_can_dlc", &log.frame_by_can_dlc, SortKind::CanDlc),
+        (
+            "id_chn_by_can_dlc",
+            &log.id_chn_by_can_dlc,
+            SortKind::CanDlc,
+        ),
+        (
+            "frame_by_can_protocol",
+            &log.frame_by_can_protocol,
+            SortKind::CanProtocol,
+        ),
+        (
+            "id_chn_by_can_protocol",
+            &log.id_chn_by_can_protocol,
+            SortKind::CanProtocol,
+        ),
+        (
+            "frame_by_protocol",
+            &log.frame_by_protocol,
+            SortKind::Protocol,
+        ),
+        (
+            "frame_by_can_priority",
+            &log.frame_by_can_priority,
+            SortKind::CanPriority,
+        ),
+        (
+            "frame_by_can_cyclic_only",
+            &log.frame_by_can_cyclic_only,
+            SortKind::Timestamp,
+        ),
+        (
+            "frame_by_can_event_driven_only",
+            &log.frame_by_can_event_driven_only,
+            SortKind::Timestamp,
+        ),
+        (
+            "frame_by_can_sender_node",
+            &log.frame_by_can_sender_node,
+            SortKind::CanSenderNode,
+        ),
+        (
+            "id_chn_by_can_sender_node",
+            &log.id_chn_by_can_sender_node,
+            SortKind::CanSenderNode,
+        ),
+        (
+            "frame_by_can_data",
+            &log.frame_by_can_data,
+            SortKind::CanData,
+        ),
+        (
+            "id_chn_by_can_data",
+            &log.id_chn_by_can_data,
+            SortKind::CanData,
+        ),
+        (
+            "frame_by_can_comment",
+            &log.frame_by_can_comment,
+            SortKind::CanComment,
+        ),
+        (
+            "id_chn_by_can_comment",
+            &log.id_chn_by_can_comment,
+            SortKind::CanComment,
+        ),
+    ];
+
+    views
+        .into_iter()
+        .filter(|(_, keys, kind)| {
+            let mut sorted: Vec<FrameKey> = keys.to_vec();
+            sorter.sort(*kind, &mut sorted);
+            sorted.as_slice() != *keys
+        })
+        .map(|(name, _, _)| format!("{}: not sorted by its key", name))
+        .collect()
+}
+
+fn rebuild(log: &mut Log, parallel: bool) {
+    let Log {
+        frames,
+        channel_map,
+        trigger_blocks,
+        frame_by_file_order,
+        frame_by_timestamp,
+        frame_by_block_timestamp,
+        frame_by_channel,
+        frame_by_direction,
+        frame_by_can_msg_name,
+        frame_by_can_signal_name,
+        frame_by_can_msg_id,
+        frame_by_can_dlc,
+        frame_by_can_protocol,
+        frame_by_protocol,
+        frame_by_can_priority,
+        frame_by_can_cyclic_only,
+        frame_by_can_event_driven_only,
+        frame_by_can_sender_node,
+        frame_by_can_data,
+        frame_by_can_comment,
+        id_chn_by_timestamp,
+        id_chn_by_channel,
+        id_chn_by_direction,
+        id_chn_by_can_msg_name,
+        id_chn_by_can_msg_id,
+        id_chn_by_can_dlc,
+        id_chn_by_can_protocol,
+        id_chn_by_can_sender_node,
+        id_chn_by_can_data,
+        id_chn_by_can_comment,
+        ..
+    } = log;
+
+    let base_keys: &[FrameKey] = frame_by_file_order.as_slice();
+    let sorter: ViewSorter =
+        ViewSorter::new(frames, channel_map, trigger_blocks, base_keys, parallel);
+    let id_chn_keys: Vec<FrameKey> = sorter.id_chn_keys(base_keys);
+    let can_keys: Vec<FrameKey> = sorter.can_keys(base_keys);
+    let (cyclic_keys, event_keys) =
+        sorter.cyclic_split(base_keys, CYCLIC_MIN_REPEAT_COUNT, CYCLIC_MAX_GAP_CV);
+
+    let jobs: Vec<(&mut Vec<FrameKey>, &[FrameKey], SortKind)> = vec![
+        (frame_by_timestamp, base_keys, SortKind::Timestamp),
+        (id_chn_by_timestamp, &id_chn_keys, SortKind::Timestamp),
+        (
+            frame_by_block_timestamp,
+            base_keys,
+            SortKind::BlockTimestamp,
+        ),
+        (frame_by_channel, base_keys, SortKind::Channel),
+        (id_chn_by_channel, &id_chn_keys, SortKind::Channel),
+        (frame_by_direction, base_keys, SortKind::Direction),
+        (id_chn_by_direction, &id_chn_keys, SortKind::Direction),
+        (frame_by_can_msg_name, &can_keys, SortKind::CanMsgName),
+        (id_chn_by_can_msg_name, &id_chn_keys, SortKind::CanMsgName),
+        (frame_by_can_signal_name, &can_keys, SortKind::CanSignalName),
+        (frame_by_can_msg_id, &can_keys, SortKind::CanMsgId),
+        (id_chn_by_can_msg_id, &id_chn_keys, SortKind::CanMsgId),
+        (frame_by_can_dlc, &can_keys, SortKind::CanDlc),
+        (id_chn_by_can_dlc, &id_chn_keys, SortKind::CanDlc),
+        (frame_by_can_protocol, &can_keys, SortKind::CanProtocol),
+        (id_chn_by_can_protocol, &id_chn_keys, SortKind::CanProtocol),
+        (frame_by_protocol, &can_keys, SortKind::Protocol),
+        (frame_by_can_priority, &can_keys, SortKind::CanPriority),
+        (frame_by_can_cyclic_only, &cyclic_keys, SortKind::Timestamp),
+        (
+            frame_by_can_event_driven_only,
+            &event_keys,
+            SortKind::Timestamp,
+        ),
+        (frame_by_can_sender_node, &can_keys, SortKind::CanSenderNode),
+        (
+            id_chn_by_can_sender_node,
+            &id_chn_keys,
+            SortKind::CanSenderNode,
+        ),
+        (frame_by_can_data, &can_keys, SortKind::CanData),
+        (id_chn_by_can_data, &id_chn_keys, SortKind::CanData),
+        (frame_by_can_comment, &can_keys, SortKind::CanComment),
+        (id_chn_by_can_comment, &id_chn_keys, SortKind::CanComment),
+    ];
+
+    #[cfg(feature = "rayon")]
+    if parallel {
+        let sorter: &ViewSorter = &sorter;
+        rayon::scope(|s| {
+            for (target, source, kind) in jobs {
+                s.spawn(move |_| {
+                    refill(target, source);
+                    sorter.sort(kind, target);
+                });
+            }
+        });
+        return;
+    }
+
+    for (target, source, kind) in jobs {
+        refill(target, source);
+        sorter.sort(kind, target);
+    }
+}
+
+fn refill(target: &mut Vec<FrameKey>, source: &[FrameKey]) {
+    target.clear();
+    target.extend_from_slice(source);
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SortKind {
+    Timestamp,
+    BlockTimestamp,
+    Channel,
+    Direction,
+    CanMsgName,
+    CanSignalName,
+    CanMsgId,
+    CanDlc,
+    CanProtocol,
+    Protocol,
+    CanPriority,
+    CanSenderNode,
+    CanData,
+    CanComment,
+}
+
+/// Resolves the sort key of each `FrameKey` through the frames and the channel
+/// databases. Ties and missing frames fall back to the file order.
+struct ViewSorter<'a> {
+    frames: &'a SlotMap<FrameKey, Frame>,
+    channel_map: &'a HashMap<u8, ChannelInfo>,
+    trigger_blocks: &'a [TriggerBlock],
+    order_index: HashMap<FrameKey, usize>,
+    #[cfg(feature = "rayon")]
+    parallel: bool,
+}
+
+impl<'a> ViewSorter<'a> {
+    fn new(
+        frames: &'a SlotMap<FrameKey, Frame>,
+        channel_map: &'a HashMap<u8, ChannelInfo>,
+        trigger_blocks: &'a [TriggerBlock],
+        base_keys: &[FrameKey],
+        parallel: bool,
+    ) -> Self {
+        let order_index: HashMap<FrameKey, usize> = base_keys
+            .iter()
+            .enumerate()
+            .map(|(idx, key)| (*key, idx))
+            .collect();
+        #[cfg(not(feature = "rayon"))]
+        let _ = parallel;
+        Self {
+            frames,
+            channel_map,
+            trigger_blocks,
+            order_index,
+            #[cfg(feature = "rayon")]
+            parallel,
+        }
+    }
+
+    fn fallback_index(&self, key: FrameKey) -> usize {
+        self.order_index.get(&key).copied().unwrap_or(usize::MAX)
+    }
+
+    /// See `Log::block_adjusted_timestamp`.
+    fn block_adjusted_timestamp(&self, frame: &Frame) -> f64 {
+        trigger::block_adjusted_timestamp(self.trigger_blocks, frame)
+    }
+
+    /// Last CAN frame of every (id, channel) pair, in file order.
+    fn id_chn_keys(&self, base_keys: &[FrameKey]) -> Vec<FrameKey> {
+        let mut last_by_id_channel: HashMap<(u32, u8), FrameKey> = HashMap::new();
+        for &key in base_keys {
+            if let Some(frame) = self.frames.get(key)
+                && frame.ftype == FrameType::Can
+            {
+                last_by_id_channel.insert((frame.id, frame.channel), key);
+            }
+        }
+        let mut id_chn_keys: Vec<FrameKey> = last_by_id_channel.values().copied().collect();
+        id_chn_keys.sort_by_key(|key| self.fallback_index(*key));
+        id_chn_keys
+    }
+
+    fn can_keys(&self, base_keys: &[FrameKey]) -> Vec<FrameKey> {
+        base_keys
+            .iter()
+            .copied()
+            .filter(
+                |key| matches!(self.frames.get(*key), Some(frame) if frame.ftype == FrameType::Can),
+            )
+            .collect()
+    }
+
+    /// CAN frames split into cyclic and event driven (id, channel) pairs, in file order.
+    ///
+    /// A pair is cyclic if it has more than `min_repeat_count` frames and the
+    /// coefficient of variation (population std / mean) of its inter-frame gaps is
+    /// below `max_gap_cv`.
+    fn cyclic_split(
+        &self,
+        base_keys: &[FrameKey],
+        min_repeat_count: usize,
+        max_gap_cv: f64,
+    ) -> (Vec<FrameKey>, Vec<FrameKey>) {
+        let mut timestamps: HashMap<(u32, u8), Vec<f64>> = HashMap::new();
+        for &key in base_keys {
+            if let Some(frame) = self.frames.get(key)
+                && frame.ftype == FrameType::Can
+            {
+                timestamps
+                    .entry((frame.id, frame.channel))
+                    .or_default()
+                    .push(self.block_adjusted_timestamp(frame));
+            }
+        }
+
+        let is_cyclic = |ts: &mut Vec<f64>| -> bool {
+            // more than one frame is needed for a gap
+            if ts.len() <= min_repeat_count.max(1) {
+                return false;
+            }
+            ts.sort_by(f64::total_cmp);
+            let gaps: Vec<f64> = ts.windows(2).map(|w| w[1] - w[0]).collect();
+            let mean: f64 = gaps.iter().sum::<f64>() / gaps.len() as f64;
+            if mean <= 0.0 {
+                return false;
+            }
+            let variance: f64 =
+                gaps.iter().map(|g| (g - mean).powi(2)).sum::<f64>() / gaps.len() as f64;
+            variance.sqrt() / mean < max_gap_cv
+        };
+        let cyclic_pairs: HashMap<(u32, u8), bool> = timestamps
+            .into_iter()
+            .map(|(pair, mut ts)| (pair, is_cyclic(&mut ts)))
+            .collect();
+
+        let mut cyclic_keys: Vec<FrameKey> = Vec::new();
+        let mut event_keys: Vec<FrameKey> = Vec::new();
+        for &key in base_keys {
+            let Some(frame) = self.frames.get(key) else {
+                continue;
+            };
+            match cyclic_pairs.get(&(frame.id, frame.channel)) {
+                Some(true) if frame.ftype == FrameType::Can => cyclic_keys.push(key),
+                Some(false) if frame.ftype == FrameType::Can => event_keys.push(key),
+                _ => {}
+            }
+        }
+        (cyclic_keys, event_keys)
+    }
+
+    fn sort(&self, kind: SortKind, vec: &mut [FrameKey]) {
+        match kind {
+            SortKind::Timestamp => self.sort_by_timestamp(vec),
+            SortKind::BlockTimestamp => self.sort_by_block_timestamp(vec),
+            SortKind::Channel => self.sort_by_channel(vec),
+            SortKind::Direction => self.sort_by_direction(vec),
+            SortKind::CanMsgName => self.sort_by_can_msg_name(vec),
+            SortKind::CanSignalName => self.sort_by_can_signal_name(vec),
+            SortKind::CanMsgId => self.sort_by_can_msg_id(vec),
+            SortKind::CanDlc => self.sort_by_can_dlc(vec),
+            SortKind::CanProtocol => self.sort_by_can_protocol(vec),
+            SortKind::Protocol => self.sort_by_protocol(vec),
+            SortKind::CanPriority => self.sort_by_can_priority(vec),
+            SortKind::CanSenderNode => self.sort_by_can_sender_node(vec),
+            SortKind::CanData => self.sort_by_can_data(vec),
+            SortKind::CanComment => self.sort_by_can_comment(vec),
+        }
+    }
+
+    fn sort_by_key<K, F>(&self, vec: &mut [FrameKey], f: F)
+    where
+        K: Ord + Send,
+        F: Fn(&FrameKey) -> K + Sync,
+    {
+        #[cfg(feature = "rayon")]
+        if self.parallel {
+            use rayon::slice::ParallelSliceMut;
+            vec.par_sort_by_key(f);
+            return;
+        }
+        vec.sort_by_key(f);
+    }
+
+    fn sort_by_timestamp(&self, vec: &mut [FrameKey]) {
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            match self.frames.get(key) {
+                Some(frame) => (
+                    0_u8,
+                    OrderedFloat(self.block_adjusted_timestamp(frame)),
+                    fallback,
+                ),
+                None => (1_u8, OrderedFloat(0.0), fallback),
+            }
+        });
+    }
+
+    fn sort_by_block_timestamp(&self, vec: &mut [FrameKey]) {
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            match self.frames.get(key) {
+                Some(frame) => (
+                    0_u8,
+                    frame.trigger_block.map_or(0, |block| block + 1),
+                    OrderedFloat(frame.timestamp),
+                    fallback,
+                ),
+                None => (1_u8, usize::MAX, OrderedFloat(0.0), fallback),
+            }
+        });
+    }
+
+    fn sort_by_channel(&self, vec: &mut [FrameKey]) {
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            match self.frames.get(key) {
+                Some(frame) => (0_u8, frame.channel, OrderedFloat(frame.timestamp), fallback),
+                None => (1_u8, u8::MAX, OrderedFloat(0.0), fallback),
+            }
+        });
+    }
+
+    fn sort_by_direction(&self, vec: &mut [FrameKey]) {
+        let direction_rank = |dir: &Direction| match dir {
+            Direction::Rx => 0_u8,
+            Direction::Tx => 1_u8,
+        };
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            match self.frames.get(key) {
+                Some(frame) => (
+                    0_u8,
+                    direction_rank(&frame.direction),
+                    OrderedFloat(frame.timestamp),
+                    fallback,
+                ),
+                None => (1_u8, u8::MAX, OrderedFloat(0.0), fallback),
+            }
+        });
+    }
+
+    fn sort_by_can_msg_name(&self, vec: &mut [FrameKey]) {
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            let (rank, name) = self
+                .frames
+                .get(key)
+                .and_then(|frame| {
+                    self.channel_map
+                        .get(&frame.channel)
+                        .and_then(|info| info.database.as_ref())
+                        .and_then(|db| db.get_message_by_key(frame.msg_key))
+                        .map(|msg| msg.name.as_str())
+                })
+                .map(|name| (0_u8, name))
+                .unwrap_or((1_u8, ""));
+            (rank, name, fallback)
+        });
+    }
+
+    fn sort_by_can_signal_name(&self, vec: &mut [FrameKey]) {
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            let (rank, name) = self
+                .frames
+                .get(key)
+                .and_then(|frame| {
+                    let db = self
+                        .channel_map
+                        .get(&frame.channel)
+                        .and_then(|info| info.database.as_ref())?;
+                    frame
+                        .sig_keys
+                        .iter()
+                        .filter_map(|sig_key| db.get_sig_by_key(*sig_key))
+                        .map(|signal| signal.name.as_str())
+                        .min()
+                })
+                .map(|name| (0_u8, name))
+                .unwrap_or((1_u8, ""));
+            (rank, name, fallback)
+        });
+    }
+
+    fn sort_by_can_msg_id(&self, vec: &mut [FrameKey]) {
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            match self.frames.get(key) {
+                Some(frame) => (0_u8, frame.id, fallback),
+                None => (1_u8, u32::MAX, fallback),
+            }
+        });
+    }
+
+    fn sort_by_can_dlc(&self, vec: &mut [FrameKey]) {
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            match self.frames.get(key) {
+                Some(frame) => (0_u8, frame.byte_length, frame.id, fallback),
+                None => (1_u8, u16::MAX, u32::MAX, fallback),
+            }
+        });
+    }
+
+    fn sort_by_can_protocol(&self, vec: &mut [FrameKey]) {
+        let protocol_rank = |frame: &Frame| -> u8 { if frame.byte_length <= 8 { 0 } else { 1 } };
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            match self.frames.get(key) {
+                Some(frame) => (0_u8, protocol_rank(frame), frame.byte_length, fallback),
+                None => (1_u8, u8::MAX, u16::MAX, fallback),
+            }
+        });
+    }
+
+    fn sort_by_protocol(&self, vec: &mut [FrameKey]) {
+        let protocol_rank = |frame: &Frame| -> u8 { if frame.byte_length <= 8 { 0 } else { 1 } };
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            match self.frames.get(key) {
+                Some(frame) => (
+                    0_u8,
+                    protocol_rank(frame),
+                    frame.channel,
+                    frame.id,
+                    fallback,
+                ),
+                None => (1_u8, u8::MAX, u8::MAX, u32::MAX, fallback),
+            }
+        });
+    }
+
+    fn sort_by_can_priority(&self, vec: &mut [FrameKey]) {
+        let priority = |frame: &Frame| -> u8 {
+            if frame.is_extended_id() {
+                ((frame.id >> 26) & 0x7) as u8
+            } else {
+                u8::MAX
+            }
+        };
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            match self.frames.get(key) {
+                Some(frame) => (0_u8, priority(frame), frame.channel, frame.id, fallback),
+                None => (1_u8, u8::MAX, u8::MAX, u32::MAX, fallback),
+            }
+        });
+    }
+
+    fn sort_by_can_sender_node(&self, vec: &mut [FrameKey]) {
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            let (rank, name) = self
+                .frames
+                .get(key)
+                .and_then(|frame| {
+                    self.channel_map
+                        .get(&frame.channel)
+                        .and_then(|info| info.database.as_ref())
+                        .and_then(|db| db.get_node_by_key(frame.tx_node_key))
+                        .map(|node| node.name.as_str())
+                })
+                .map(|name| (0_u8, name))
+                .unwrap_or((1_u8, ""));
+            (rank, name, fallback)
+        });
+    }
+
+    fn sort_by_can_data(&self, vec: &mut [FrameKey]) {
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            match self.frames.get(key) {
+                Some(frame) => (0_u8, frame.data.as_str(), fallback),
+                None => (1_u8, "", fallback),
+            }
+        });
+    }
+
+    fn sort_by_can_comment(&self, vec: &mut [FrameKey]) {
+        self.sort_by_key(vec, |key| {
+            let key = *key;
+            let fallback = self.fallback_index(key);
+            let (rank, comment) = self
+                .frames
+                .get(key)
+                .and_then(|frame| {
+                    self.channel_map
+                        .get(&frame.channel)
+                        .and_then(|info| info.database.as_ref())
+                        .and_then(|db| db.get_message_by_key(frame.msg_key))
+                        .map(|msg| msg.comment.as_str())
+                })
+                .map(|comment| (0_u8, comment))
+                .unwrap_or((1_u8, ""));
+            (rank, comment, fallback)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::frame::FrameBuilder;
+
+    #[test]
+    fn cyclic_pairs_need_more_than_min_repeat_count_frames() {
+        let frame = |timestamp: f64, id: u32| {
+            FrameBuilder::new()
+                .timestamp(timestamp)
+                .channel(1)
+                .id(id)
+                .data("00")
+                .build()
+        };
+        // 0x100 is sent exactly CYCLIC_MIN_REPEAT_COUNT times, 0x200 once more
+        let mut frames: Vec<Frame> = (0..3).map(|i| frame(f64::from(i) * 0.1, 0x100)).collect();
+        frames.extend((0..4).map(|i| frame(f64::from(i) * 0.1 + 0.05, 0x200)));
+        let log: Log = Log::from_frames(frames);
+
+        let ids = |view: &[FrameKey]| -> Vec<u32> {
+            view.iter().map(|key| log.frames[*key].id).collect()
+        };
+        assert_eq!(ids(&log.frame_by_can_cyclic_only), [0x200; 4]);
+        assert_eq!(ids(&log.frame_by_can_event_driven_only), [0x100; 3]);
+    }
+
+    #[test]
+    fn can_dlc_view_orders_same_dlc_by_id() {
+        let frame = |timestamp: f64, id: u32, data: &str| {
+            FrameBuilder::new()
+                .timestamp(timestamp)
+                .channel(1)
+                .id(id)
+                .data(data)
+                .build()
+        };
+        let log: Log = Log::from_frames(vec![
+            frame(0.1, 0x300, "00 11"),
+            frame(0.2, 0x100, "00 11 22"),
+            frame(0.3, 0x200, "00 11"),
+            frame(0.4, 0x100, "00 11"),
+            frame(0.5, 0x200, "00 11"),
+        ]);
+        let order: Vec<(u16, u32, f64)> = log
+            .frame_by_can_dlc
+            .iter()
+            .map(|key| {
+                let frame: &Frame = &log.frames[*key];
+                (frame.byte_length, frame.id, frame.timestamp)
+            })
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (2, 0x100, 0.4),
+                (2, 0x200, 0.3),
+                (2, 0x200, 0.5),
+                (2, 0x300, 0.1),
+                (3, 0x100, 0.2),
+            ]
+        );
+    }
+
+    /// Sequential and parallel rebuild of a 1M-frame trace, the trace used to time
+    /// `from_asc_file_parallel`. Run with
+    /// `cargo test --release --features rayon -- --ignored`.
+    #[cfg(feature = "rayon")]
+    #[test]
+    #[ignore]
+    fn parallel_rebuild_matches_sequential() {
+        let frames: Vec<Frame> = (0..1_000_000_u32)
+            .map(|i| {
+                FrameBuilder::new()
+                    .timestamp(f64::from(i.wrapping_mul(7919) % 1_000_000) * 1e-4)
+                    .channel((i % 4) as u8 + 1)
+                    .id(i % 2048)
+                    .data("00 11 22 33 44 55 66 77")
+                    .build()
+            })
+            .collect();
+        let mut log: Log = Log::from_frames(frames);
+
+        rebuild(&mut log, false);
+        let expected: Vec<FrameKey> = log.frame_by_timestamp.clone();
+        rebuild(&mut log, true);
+        assert_eq!(log.frame_by_timestamp, expected);
+    }
+}
@@ -0,0 +1,61 @@
+use std::io::Write;
+
+use crate::types::frame::{Frame, FrameType};
+use crate::types::log::Log;
+
+impl Log {
+    /// Writes the CAN and remote frames in SocketCAN `candump -l` format, in file order:
+    /// `(timestamp) canN id#payload`.
+    ///
+    /// - the timestamp is Unix epoch seconds from `absolute_time` plus the frame
+    ///   timestamp, or the relative timestamp when no `date` header was found;
+    /// - `N` is the logger channel number;
+    /// - extended ids are written with 8 hex digits, standard ids with 3;
+    /// - CAN-FD frames (more than 8 bytes) use the `id##1payload` form;
+    /// - remote frames use the `id#R` form, followed by the DLC when it is not 0.
+    pub fn to_candump_format(&self, writer: &mut impl Write) -> Result<(), std::io::Error> {
+        let base_epoch: f64 = self
+            .absolute_time
+            .to_unix_timestamp_ms()
+            .map(|ms| ms as f64 / 1000.0)
+            .unwrap_or(0.0);
+
+        for key in &self.frame_by_file_order {
+            let Some(frame) = self.frames.get(*key) else {
+                continue;
+            };
+            if !matches!(frame.ftype, FrameType::Can | FrameType::Remote) {
+                continue;
+            }
+            writeln!(
+                writer,
+                "({:.6}) can{} {}",
+                base_epoch + self.block_adjusted_timestamp(frame),
+                frame.channel,
+                candump_frame(frame)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn candump_frame(frame: &Frame) -> String {
+    let id: String = if frame.is_extended_id() {
+        format!("{:08X}", frame.id)
+    } else {
+        format!("{:03X}", frame.id)
+    };
+    if frame.ftype == FrameType::Remote {
+        return if frame.byte_length > 0 {
+            format!("{}#R{:X}", id, frame.byte_length)
+        } else {
+            format!("{}#R", id)
+        };
+    }
+    let payload: String = frame.data.split_ascii_whitespace().collect();
+    if frame.byte_length > 8 {
+        format!("{}##1{}", id, payload)
+    } else {
+        format!("{}#{}", id, payload)
+    }
+}
@@ -0,0 +1,158 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Write;
+
+use dbc_editor::types::database::MessageKey;
+
+use crate::types::errors::CsvExportError;
+use crate::types::frame::FrameType;
+use crate::types::log::Log;
+
+impl Log {
+    /// Writes every decoded signal as a "wide" CSV table.
+    ///
+    /// - the first column is the timestamp, one row per distinct timestamp of
+    ///   any signal, in ascending order;
+    /// - every other column is a signal, named `<channel display name>_<signal name>`
+    ///   and ordered by channel number then signal name;
+    /// - a cell is empty when the signal has no sample at that timestamp.
+    ///
+    /// Header fields are quoted as in RFC 4180 when needed.
+    pub fn to_csv_columnar(&self, writer: &mut impl Write) -> Result<(), CsvExportError> {
+        let mut columns: Vec<(u8, String, &[(f64, f64)])> = Vec::new();
+        for (channel, sig_key) in self.observed_signals() {
+            let Some(signal) = self
+                .get_database_by_channel(channel)
+                .and_then(|db| db.get_sig_by_key(sig_key))
+            else {
+                continue;
+            };
+            columns.push((channel, signal.name.clone(), &signal.values));
+        }
+        columns.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+        let mut timestamps: Vec<f64> = columns
+            .iter()
+            .flat_map(|(_, _, values)| values.iter().map(|(t, _)| *t))
+            .collect();
+        timestamps.sort_by(f64::total_cmp);
+        timestamps.dedup();
+
+        write!(writer, "timestamp")?;
+        for (channel, name, _) in &columns {
+            let header: String = format!("{}_{}", self.channel_display_name(*channel), name);
+            write!(writer, ",{}", escape_field(&header))?;
+        }
+        writeln!(writer)?;
+
+        // one cursor per column, the samples of each signal are in time order
+        let mut cursors: Vec<usize> = vec![0; columns.len()];
+        for t in timestamps {
+            write!(writer, "{}", t)?;
+            for ((_, _, values), cursor) in columns.iter().zip(cursors.iter_mut()) {
+                while *cursor < values.len() && values[*cursor].0 < t {
+                    *cursor += 1;
+                }
+                match values.get(*cursor) {
+                    Some(&(ts, value)) if ts == t => {
+                        write!(writer, ",{}", value)?;
+                        *cursor += 1;
+                    }
+                    _ => write!(writer, ",")?,
+                }
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the coverage of the `channel` database by the trace as CSV, one row
+    /// per database message ordered by id:
+    /// `message_name,message_id,frame_count,signals_covered,total_signals,coverage_pct`.
+    ///
+    /// - `frame_count` is 0 for messages never seen in the trace;
+    /// - a signal is covered when at least one of its decoded raw values is not 0;
+    /// - `coverage_pct` is `signals_covered / total_signals * 100`, 0 for messages
+    ///   without signals.
+    ///
+    /// Only the header is written if the channel has no database. Message names are
+    /// quoted as in RFC 4180 when needed.
+    pub fn export_dbc_coverage_report(
+        &self,
+        channel: u8,
+        writer: &mut impl Write,
+    ) -> Result<(), CsvExportError> {
+        writeln!(
+            writer,
+            "message_name,message_id,frame_count,signals_covered,total_signals,coverage_pct"
+        )?;
+        let Some(db) = self.get_database_by_channel(channel) else {
+            return Ok(());
+        };
+
+        let mut frame_counts: HashMap<MessageKey, usize> = HashMap::new();
+        for frame in self.frames.values() {
+            if frame.channel == channel && frame.ftype == FrameType::Can {
+                *frame_counts.entry(frame.msg_key).or_insert(0) += 1;
+            }
+        }
+
+        let mut messages: Vec<_> = db.messages.iter().collect();
+        messages.sort_by_key(|(_, msg)| msg.id);
+        for (msg_key, msg) in messages {
+            let total_signals: usize = msg.signals.len();
+            let signals_covered: usize = msg
+                .signals
+                .iter()
+                .filter_map(|sig_key| db.get_sig_by_key(*sig_key))
+                .filter(|signal| signal.raws.iter().any(|(_, raw)| *raw != 0))
+                .count();
+            let coverage_pct: f64 = if total_signals == 0 {
+                0.0
+            } else {
+                signals_covered as f64 / total_signals as f64 * 100.0
+            };
+            writeln!(
+                writer,
+                "{},0x{:X},{},{},{},{:.1}",
+                escape_field(&msg.name),
+                msg.id,
+                frame_counts.get(&msg_key).copied().unwrap_or(0),
+                signals_covered,
+                total_signals,
+                coverage_pct
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Quote `field` as in RFC 4180 if it contains a comma, a double quote or a line
+/// break, doubling the inner double quotes.
+fn escape_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_field_follows_rfc_4180() {
+        assert_eq!(escape_field("EngineSpeed"), "EngineSpeed");
+        assert_eq!(escape_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn empty_log_writes_header_only() {
+        let mut out: Vec<u8> = Vec::new();
+        Log::default().to_csv_columnar(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "timestamp\n");
+    }
+}
@@ -0,0 +1,87 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use dbc_editor::types::database::SignalKey;
+
+use crate::types::errors::GnuplotExportError;
+use crate::types::log::Log;
+
+impl Log {
+    /// Writes a self-contained gnuplot script plotting the decoded `signal_keys`
+    /// over time, one line per signal.
+    ///
+    /// - each signal is embedded as a `$dataN << EOD` block of `time,value` rows,
+    ///   with the signal name (and unit) as column header;
+    /// - running the script renders a png next to `path`, with the same stem.
+    ///
+    /// Fails with `GnuplotExportError::NoSignals`, without writing `path`, if
+    /// `signal_keys` is empty.
+    pub fn export_gnuplot(
+        &self,
+        signal_keys: &[SignalKey],
+        path: &str,
+    ) -> Result<(), GnuplotExportError> {
+        if signal_keys.is_empty() {
+            return Err(GnuplotExportError::NoSignals);
+        }
+        let mut series: Vec<(String, &[(f64, f64)])> = Vec::with_capacity(signal_keys.len());
+        for sig_key in signal_keys {
+            let mut channels: Vec<u8> = self.channel_map.keys().copied().collect();
+            channels.sort_unstable();
+            let signal = channels
+                .into_iter()
+                .find_map(|ch| self.get_database_by_channel(ch)?.get_sig_by_key(*sig_key))
+                .ok_or(GnuplotExportError::UnknownSignal(*sig_key))?;
+            let title: String = if signal.unit.is_empty() {
+                signal.name.clone()
+            } else {
+                format!("{} [{}]", signal.name, signal.unit)
+            };
+            series.push((title, signal.values.as_slice()));
+        }
+
+        let image: String = Path::new(path)
+            .with_extension("png")
+            .to_string_lossy()
+            .into_owned();
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "set terminal png")?;
+        writeln!(writer, "set output {:?}", image)?;
+        writeln!(writer, "set datafile separator \",\"")?;
+        writeln!(writer, "set key autotitle columnheader")?;
+        writeln!(writer, "set xlabel \"time [s]\"")?;
+        for (idx, (title, values)) in series.iter().enumerate() {
+            writeln!(writer)?;
+            writeln!(writer, "$data{} << EOD", idx)?;
+            writeln!(writer, "time,{}", title.replace(',', " "))?;
+            for (timestamp, value) in values.iter() {
+                writeln!(writer, "{},{}", timestamp, value)?;
+            }
+            writeln!(writer, "EOD")?;
+        }
+        writeln!(writer)?;
+        let plots: Vec<String> = (0..series.len())
+            .map(|idx| format!("$data{} using 1:2 with lines", idx))
+            .collect();
+        writeln!(writer, "plot {}", plots.join(", \\\n     "))?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_signals_is_an_error() {
+        let path: String = std::env::temp_dir()
+            .join(format!("trace_analyzer_empty_{}.gp", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        let result: Result<(), GnuplotExportError> = Log::default().export_gnuplot(&[], &path);
+        assert!(matches!(result, Err(GnuplotExportError::NoSignals)));
+        assert!(!Path::new(&path).exists());
+    }
+}
@@ -0,0 +1,225 @@
+use std::io::Write;
+
+use dbc_editor::types::database::{DatabaseDBC, NodeKey};
+
+use crate::types::frame::{Frame, FrameType};
+use crate::types::keys::FrameKey;
+use crate::types::log::Log;
+
+/// Participant used for frames without a DBC sender or receiver.
+const UNKNOWN_NODE: &str = "UNKNOWN";
+
+impl Log {
+    /// Writes the CAN frames with `time_start <= timestamp <= time_end` as a
+    /// Mermaid `sequenceDiagram`, in timestamp order.
+    ///
+    /// - participants are the DBC node names, declared in order of first appearance
+    ///   with an identifier made of the name's ASCII alphanumerics and `_`, and the
+    ///   name itself as display text;
+    /// - every frame is an arrow from each sender node to each receiver node of its
+    ///   message, labelled with the message name and the timestamp;
+    /// - frames without DBC message use `UNKNOWN` as sender and receiver and are
+    ///   labelled with the raw id in hex (`0x18FF0010`), missing sender or receiver
+    ///   nodes also fall back to `UNKNOWN`.
+    pub fn to_mermaid_sequence_diagram(
+        &self,
+        time_start: f64,
+        time_end: f64,
+        writer: &mut impl Write,
+    ) -> Result<(), std::io::Error> {
+        let view: &[FrameKey] = &self.frame_by_timestamp;
+        let timestamp = |key: &FrameKey| {
+            self.frames
+                .get(*key)
+                .map_or(f64::MAX, |f| self.block_adjusted_timestamp(f))
+        };
+        let first: usize = view.partition_point(|key| timestamp(key) < time_start);
+        let last: usize = view.partition_point(|key| timestamp(key) <= time_end);
+
+        let mut participants: Vec<String> = Vec::new();
+        let mut arrows: Vec<(String, String, String)> = Vec::new();
+        for key in &view[first..last.max(first)] {
+            let Some(frame) = self.frames.get(*key) else {
+                continue;
+            };
+            if frame.ftype != FrameType::Can {
+                continue;
+            }
+            let (label, senders, receivers) = match self.get_database_by_channel(frame.channel) {
+                Some(db) if db.get_message_by_key(frame.msg_key).is_some() => (
+                    frame.msg_name_to_string(db),
+                    sender_names(frame, db),
+                    receiver_names(frame, db),
+                ),
+                _ => (
+                    format!("0x{:X}", frame.id),
+                    vec![UNKNOWN_NODE.to_string()],
+                    vec![UNKNOWN_NODE.to_string()],
+                ),
+            };
+            for name in senders.iter().chain(&receivers) {
+                if !participants.contains(name) {
+                    participants.push(name.clone());
+                }
+            }
+            let label: String =
+                format!("{} ({:.6} s)", label, self.block_adjusted_timestamp(frame));
+            for sender in &senders {
+                for receiver in &receivers {
+                    arrows.push((sender.clone(), receiver.clone(), label.clone()));
+                }
+            }
+        }
+
+        let ids: Vec<String> = participant_ids(&participants);
+        let id_of = |name: &str| -> String {
+            participants
+                .iter()
+                .position(|participant| participant == name)
+                .map_or_else(|| UNKNOWN_NODE.to_string(), |index| ids[index].clone())
+        };
+
+        writeln!(writer, "sequenceDiagram")?;
+        for (name, id) in participants.iter().zip(&ids) {
+            writeln!(writer, "    participant {} as {}", id, escape_text(name))?;
+        }
+        for (sender, receiver, label) in &arrows {
+            writeln!(
+                writer,
+                "    {}->>{}: {}",
+                id_of(sender),
+                id_of(receiver),
+                escape_text(label)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Names of the sender nodes of the message of `frame`, `UNKNOWN` if it has none.
+fn sender_names(frame: &Frame, db: &DatabaseDBC) -> Vec<String> {
+    let names: Vec<String> = db
+        .get_message_by_key(frame.msg_key)
+        .map(|msg| node_names(db, msg.sender_nodes.iter().copied()))
+        .unwrap_or_default();
+    or_unknown(names)
+}
+
+/// Names of the nodes receiving at least one signal of the message of `frame`,
+/// `UNKNOWN` if it has none.
+fn receiver_names(frame: &Frame, db: &DatabaseDBC) -> Vec<String> {
+    let names: Vec<String> = db
+        .get_message_by_key(frame.msg_key)
+        .map(|msg| {
+            node_names(
+                db,
+                msg.signals
+                    .iter()
+                    .filter_map(|sig_key| db.get_sig_by_key(*sig_key))
+                    .flat_map(|signal| signal.receiver_nodes.iter().copied()),
+            )
+        })
+        .unwrap_or_default();
+    or_unknown(names)
+}
+
+/// Unique names of `node_keys`, in order of first occurrence.
+fn node_names(db: &DatabaseDBC, node_keys: impl Iterator<Item = NodeKey>) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for node_key in node_keys {
+        if let Some(node) = db.get_node_by_key(node_key)
+            && !names.contains(&node.name)
+        {
+            names.push(node.name.clone());
+        }
+    }
+    names
+}
+
+/// Mermaid identifier of every participant: the ASCII alphanumerics of its name,
+/// other characters replaced by `_`, made unique with a `_<n>` suffix.
+fn participant_ids(participants: &[String]) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::with_capacity(participants.len());
+    for name in participants {
+        let mut base: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if base.is_empty() {
+            base.push('_');
+        }
+        let mut id: String = base.clone();
+        let mut suffix: usize = 1;
+        while ids.contains(&id) {
+            suffix += 1;
+            id = format!("{}_{}", base, suffix);
+        }
+        ids.push(id);
+    }
+    ids
+}
+
+/// Replace the characters ending or breaking a Mermaid statement by their entity code.
+fn escape_text(text: &str) -> String {
+    let mut escaped: String = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '#' => escaped.push_str("#35;"),
+            ';' => escaped.push_str("#59;"),
+            '\n' | '\r' => escaped.push(' '),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn or_unknown(names: Vec<String>) -> Vec<String> {
+    if names.is_empty() {
+        vec![UNKNOWN_NODE.to_string()]
+    } else {
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::frame::FrameBuilder;
+
+    #[test]
+    fn unknown_frames_use_hex_id() {
+        let log: Log = Log::from_frames(vec![
+            FrameBuilder::new()
+                .timestamp(0.1)
+                .channel(1)
+                .id(0x123)
+                .build(),
+            FrameBuilder::new()
+                .timestamp(0.2)
+                .channel(1)
+                .id(0x18FF0010)
+                .build(),
+        ]);
+        let mut out: Vec<u8> = Vec::new();
+        log.to_mermaid_sequence_diagram(0.0, 1.0, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "sequenceDiagram\n\
+             \x20   participant UNKNOWN as UNKNOWN\n\
+             \x20   UNKNOWN->>UNKNOWN: 0x123 (0.100000 s)\n\
+             \x20   UNKNOWN->>UNKNOWN: 0x18FF0010 (0.200000 s)\n"
+        );
+    }
+
+    #[test]
+    fn participant_names_are_sanitized() {
+        let names: Vec<String> = ["Gateway ECU", "Gateway-ECU", "BMS;x", ""]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            participant_ids(&names),
+            vec!["Gateway_ECU", "Gateway_ECU_2", "BMS_x", "_"]
+        );
+        assert_eq!(escape_text("BMS;x #1"), "BMS#59;x #35;1");
+    }
+}
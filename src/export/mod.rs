@@ -0,0 +1,9 @@
+pub mod candump;
+pub mod csv;
+pub mod gnuplot;
+#[cfg(feature = "mermaid")]
+pub mod mermaid;
+#[cfg(feature = "wav")]
+pub mod wav;
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
@@ -0,0 +1,64 @@
+use hound::{SampleFormat, WavSpec, WavWriter};
+
+use crate::types::errors::WavExportError;
+use crate::types::log::Log;
+use crate::utils::resample;
+
+impl Log {
+    /// Writes a decoded signal as a mono 16-bit PCM `.wav` file.
+    ///
+    /// - the signal is resampled at `sample_rate` Hz with zero-order hold over its
+    ///   own time span;
+    /// - values are normalised to `[-1.0, 1.0]` from the observed min/max, a
+    ///   constant signal is written as silence.
+    pub fn export_signal_to_wav(
+        &self,
+        sig_name: &str,
+        channel: u8,
+        sample_rate: u32,
+        path: &str,
+    ) -> Result<(), WavExportError> {
+        if sample_rate == 0 {
+            return Err(WavExportError::InvalidSampleRate);
+        }
+        let values: &[(f64, f64)] = self
+            .signal_key_by_name(sig_name, channel)
+            .and_then(|sig_key| {
+                self.get_database_by_channel(channel)?
+                    .get_sig_by_key(sig_key)
+            })
+            .map(|signal| signal.values.as_slice())
+            .filter(|values| !values.is_empty())
+            .ok_or_else(|| WavExportError::UnknownSignal {
+                name: sig_name.to_string(),
+                channel,
+            })?;
+
+        let start: f64 = values[0].0;
+        let end: f64 = values[values.len() - 1].0;
+        let (min, max) = values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, v)| {
+                (min.min(*v), max.max(*v))
+            });
+        let range: f64 = max - min;
+
+        let spec: WavSpec = WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(path, spec)?;
+        for (_, value) in resample::zoh(values, start, end, 1.0 / f64::from(sample_rate)) {
+            let normalised: f64 = if range > 0.0 {
+                2.0 * (value - min) / range - 1.0
+            } else {
+                0.0
+            };
+            writer.write_sample((normalised * f64::from(i16::MAX)).round() as i16)?;
+        }
+        writer.finalize()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+
+use crate::types::errors::XlsxExportError;
+use crate::types::log::Log;
+use crate::utils::resample;
+
+/// Seconds in a day, Excel stores times as fractions of a day.
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+impl Log {
+    /// Writes every decoded signal to an `.xlsx` file as a matrix resampled on a
+    /// common time grid of `interval_s` spacing, with zero-order hold.
+    ///
+    /// - the first column is the time from the start of the measurement, formatted
+    ///   as `HH:MM:SS.000`;
+    /// - then one column per signal, in order of first appearance, with header
+    ///   `"<channel display name>_<signal name>"`, see `ChannelInfo::display_name`;
+    /// - grid points before the first sample of a signal are left blank.
+    pub fn to_excel_signal_matrix(
+        &self,
+        path: &str,
+        interval_s: f64,
+    ) -> Result<(), XlsxExportError> {
+        if interval_s.is_nan() || interval_s <= 0.0 {
+            return Err(XlsxExportError::InvalidInterval);
+        }
+
+        let mut series: Vec<(String, &[(f64, f64)])> = Vec::new();
+        let mut start: f64 = f64::INFINITY;
+        let mut end: f64 = f64::NEG_INFINITY;
+        for (channel, sig_key) in self.observed_signals() {
+            let Some(signal) = self
+                .get_database_by_channel(channel)
+                .and_then(|db| db.get_sig_by_key(sig_key))
+            else {
+                continue;
+            };
+            let (Some(first), Some(last)) = (signal.values.first(), signal.values.last()) else {
+                continue;
+            };
+            start = start.min(first.0);
+            end = end.max(last.0);
+            let header: String = format!("{}_{}", self.channel_display_name(channel), signal.name);
+            series.push((header, &signal.values));
+        }
+
+        let mut workbook: Workbook = Workbook::new();
+        let worksheet: &mut Worksheet = workbook.add_worksheet();
+        let time_format: Format = Format::new().set_num_format("HH:MM:SS.000");
+        worksheet.write_string(0, 0, "time")?;
+        for (col, (name, values)) in series.iter().enumerate() {
+            let col: u16 = col as u16 + 1;
+            worksheet.write_string(0, col, name)?;
+            for (row, (timestamp, value)) in resample::zoh(values, start, end, interval_s)
+                .into_iter()
+                .enumerate()
+            {
+                let row: u32 = row as u32 + 1;
+                if col == 1 {
+                    worksheet.write_number_with_format(
+                        row,
+                        0,
+                        timestamp / SECONDS_PER_DAY,
+                        &time_format,
+                    )?;
+                }
+                if !value.is_nan() {
+                    worksheet.write_number(row, col, value)?;
+                }
+            }
+        }
+        workbook.save(path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use std::io::BufRead;
+
+use crate::core::{line, sort};
+use crate::types::errors::CsvImportError;
+use crate::types::frame::{Direction, Frame, FrameType};
+use crate::types::keys::FrameKey;
+use crate::types::log::Log;
+
+impl Log {
+    /// Builds a `Log` from a row-oriented CSV table, one frame per row.
+    ///
+    /// The header row names the columns, in any order:
+    /// - `timestamp` and `id` are required;
+    /// - `channel`, `direction` (`Rx`/`Tx`), `type` (`Can`/`Eth`/`ErrorFrame`),
+    ///   `dlc` and `data` (space separated hex pairs) are optional, unknown
+    ///   columns are ignored.
+    ///
+    /// `channel_map` is left empty since the CSV carries no database information.
+    pub fn from_csv(reader: impl BufRead) -> Result<Log, CsvImportError> {
+        let mut log: Log = Log::default();
+        let mut rows = reader.split(b'\n').enumerate();
+
+        let header: String = match rows.next() {
+            Some((_, row)) => decode_row(row?, 1)?,
+            None => return Err(CsvImportError::MalformedHeader),
+        };
+        let columns: Vec<String> = split_row(&header)
+            .into_iter()
+            .map(|name| name.to_ascii_lowercase())
+            .collect();
+        let column = |name: &str| columns.iter().position(|c| c == name);
+        let (Some(ts_col), Some(id_col)) = (column("timestamp"), column("id")) else {
+            return Err(CsvImportError::MalformedHeader);
+        };
+        let channel_col: Option<usize> = column("channel");
+        let direction_col: Option<usize> = column("direction");
+        let type_col: Option<usize> = column("type");
+        let dlc_col: Option<usize> = column("dlc");
+        let data_col: Option<usize> = column("data");
+
+        for (index, row) in rows {
+            let line_number: usize = index + 1;
+            let row: String = decode_row(row?, line_number)?;
+            if row.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<String> = split_row(&row);
+            let field = |col: Option<usize>| col.and_then(|c| fields.get(c)).map(|f| f.trim());
+            let malformed = |name: &str| CsvImportError::MalformedRow {
+                line: line_number,
+                field: name.to_string(),
+            };
+
+            let mut frame: Frame = Frame::default();
+            frame.timestamp = field(Some(ts_col))
+                .and_then(|f| f.parse().ok())
+                .ok_or_else(|| malformed("timestamp"))?;
+            let id_hex: &str = field(Some(id_col)).ok_or_else(|| malformed("id"))?;
+            frame.id = line::parse_id_u32(id_hex).ok_or_else(|| malformed("id"))?;
+            frame.id_hex = id_hex.to_string();
+            if let Some(f) = field(channel_col).filter(|f| !f.is_empty()) {
+                frame.channel = f.parse().map_err(|_| malformed("channel"))?;
+            }
+            frame.direction = match field(direction_col).unwrap_or("Rx") {
+                "" | "Rx" => Direction::Rx,
+                "Tx" => Direction::Tx,
+                _ => return Err(malformed("direction")),
+            };
+            frame.ftype = match field(type_col).unwrap_or("Can") {
+                "" | "Can" => FrameType::Can,
+                "Eth" => FrameType::Eth,
+                "ErrorFrame" => FrameType::ErrorFrame,
+                "Remote" => FrameType::Remote,
+                _ => return Err(malformed("type")),
+            };
+            frame.data = field(data_col).unwrap_or_default().to_string();
+            if frame
+                .data
+                .split_ascii_whitespace()
+                .any(|tok| u8::from_str_radix(tok, 16).is_err())
+            {
+                return Err(malformed("data"));
+            }
+            frame.byte_length = match field(dlc_col).filter(|f| !f.is_empty()) {
+                Some(f) => f.parse().map_err(|_| malformed("dlc"))?,
+                None => frame.data.split_ascii_whitespace().count() as u16,
+            };
+            frame.absolute_time = line::seconds_to_hms_string(frame.timestamp);
+
+            let frame_key: FrameKey = log.frames.insert(frame);
+            log.frame_by_file_order.push(frame_key);
+        }
+
+        sort::rebuild_views(&mut log);
+        Ok(log)
+    }
+}
+
+/// Decode one raw row, without its line terminator.
+fn decode_row(mut bytes: Vec<u8>, line: usize) -> Result<String, CsvImportError> {
+    if bytes.last() == Some(&b'\r') {
+        bytes.pop();
+    }
+    String::from_utf8(bytes).map_err(|_| CsvImportError::Utf8Error { line })
+}
+
+/// Split a CSV row on commas, honouring double quoted fields and `""` escapes.
+fn split_row(row: &str) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+    let mut current: String = String::new();
+    let mut quoted: bool = false;
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
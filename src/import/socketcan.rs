@@ -0,0 +1,162 @@
+use std::io::BufRead;
+
+use crate::core::{line, sort};
+use crate::types::absolute_time::AbsoluteTime;
+use crate::types::errors::SocketCanImportError;
+use crate::types::frame::{Frame, FrameType};
+use crate::types::keys::FrameKey;
+use crate::types::log::Log;
+
+impl Log {
+    /// Builds a `Log` from a SocketCAN `candump -l` log:
+    /// `(1234567890.123456) vcan0 001#DEADBEEF`.
+    ///
+    /// - timestamps are made relative to the first frame, whose epoch becomes
+    ///   `absolute_time`;
+    /// - the trailing number of the interface name is the channel number, every
+    ///   channel is configured as a CAN channel without database and with the
+    ///   interface name as `ChannelInfo::namespace`;
+    /// - 8 digit ids are extended ids and get the `x` marker in `id_hex`;
+    /// - CAN-FD frames (`id##<flags><data>`) set `brs_enabled` / `esi_error` from
+    ///   the flags nibble;
+    /// - remote requests (`id#R`, `id#R<dlc>`) become `FrameType::Remote` frames
+    ///   without payload, whose `byte_length` is the requested DLC (0 if absent).
+    pub fn from_socketcan_log(reader: impl BufRead) -> Result<Log, SocketCanImportError> {
+        let mut log: Log = Log::default();
+        let mut first_epoch: Option<f64> = None;
+
+        for (index, row) in reader.lines().enumerate() {
+            let row: String = row?;
+            let line_number: usize = index + 1;
+            let mut it = row.split_ascii_whitespace();
+            let Some(ts_tok) = it.next() else {
+                continue;
+            };
+
+            let epoch: f64 = ts_tok
+                .strip_prefix('(')
+                .and_then(|tok| tok.strip_suffix(')'))
+                .and_then(|tok| tok.parse().ok())
+                .ok_or(SocketCanImportError::MalformedTimestamp { line: line_number })?;
+            let interface: &str = it
+                .next()
+                .ok_or(SocketCanImportError::MalformedInterface { line: line_number })?;
+            let channel: u8 = interface_number(interface)
+                .ok_or(SocketCanImportError::MalformedInterface { line: line_number })?;
+            let mut frame: Frame = it
+                .next()
+                .and_then(parse_candump_frame)
+                .ok_or(SocketCanImportError::MalformedFrame { line: line_number })?;
+
+            let base: f64 = *first_epoch.get_or_insert_with(|| {
+                log.absolute_time =
+                    AbsoluteTime::from_unix_timestamp_ms((epoch * 1000.0).round() as i64);
+                epoch
+            });
+            frame.timestamp = epoch - base;
+            frame.channel = channel;
+            frame.absolute_time =
+                line::frame_absolute_time(log.absolute_time.value, frame.timestamp);
+
+            if !log.channel_map.contains_key(&channel) {
+                log.add_can_channel(channel, None);
+                if let Some(info) = log.channel_map.get_mut(&channel) {
+                    info.namespace = Some(interface.to_string());
+                }
+            }
+            let frame_key: FrameKey = log.frames.insert(frame);
+            log.frame_by_file_order.push(frame_key);
+        }
+
+        sort::rebuild_views(&mut log);
+        Ok(log)
+    }
+}
+
+/// Channel number from the trailing digits of an interface name, e.g. `vcan1` -> 1.
+fn interface_number(interface: &str) -> Option<u8> {
+    let name: &str = interface.trim_end_matches(|c: char| c.is_ascii_digit());
+    interface[name.len()..].parse().ok()
+}
+
+/// Parse `id#data`, `id##<flags><data>` or `id#R[<dlc>]`.
+fn parse_candump_frame(token: &str) -> Option<Frame> {
+    let (id_tok, rest) = token.split_once('#')?;
+    if id_tok.is_empty() || !id_tok.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut frame: Frame = Frame {
+        ftype: FrameType::Can,
+        id: u32::from_str_radix(id_tok, 16).ok()?,
+        id_hex: if id_tok.len() == 8 {
+            format!("{}x", id_tok)
+        } else {
+            id_tok.to_string()
+        },
+        ..Frame::default()
+    };
+
+    let data: &str = if let Some(fd) = rest.strip_prefix('#') {
+        let mut chars = fd.chars();
+        let flags: u32 = chars.next()?.to_digit(16)?;
+        frame.brs_enabled = flags & 0x1 != 0;
+        frame.esi_error = flags & 0x2 != 0;
+        chars.as_str()
+    } else if let Some(dlc) = rest.strip_prefix(['R', 'r']) {
+        frame.ftype = FrameType::Remote;
+        frame.byte_length = if dlc.is_empty() {
+            0
+        } else {
+            u16::from_str_radix(dlc, 16)
+                .ok()
+                .filter(|dlc| *dlc <= 0xF)?
+        };
+        return Some(frame);
+    } else {
+        rest
+    };
+
+    // candump allows '.' between the bytes
+    let hex: String = data.chars().filter(|c| *c != '.').collect();
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    let bytes: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    frame.set_payload(&bytes);
+    Some(frame)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_request_keeps_its_dlc() {
+        let frame: Frame = parse_candump_frame("123#R4").unwrap();
+        assert_eq!(frame.ftype, FrameType::Remote);
+        assert_eq!(frame.id, 0x123);
+        assert_eq!(frame.byte_length, 4);
+        assert!(frame.data.is_empty());
+
+        let frame: Frame = parse_candump_frame("123#R").unwrap();
+        assert_eq!(frame.ftype, FrameType::Remote);
+        assert_eq!(frame.byte_length, 0);
+    }
+
+    #[test]
+    fn remote_request_round_trips_through_candump() {
+        let input: &str = "(1700000000.000000) can1 123#R4\n(1700000000.010000) can1 456#R\n";
+        let log: Log = Log::from_socketcan_log(input.as_bytes()).unwrap();
+        let mut out: Vec<u8> = Vec::new();
+        log.to_candump_format(&mut out).unwrap();
+        let lines: Vec<String> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| line.split_once(' ').unwrap().1.to_string())
+            .collect();
+        assert_eq!(lines, ["can1 123#R4", "can1 456#R"]);
+    }
+}
@@ -1,3 +1,9 @@
+pub mod blf;
+#[cfg(feature = "bincode")]
+pub mod checkpoint;
 pub mod core;
+pub mod export;
+pub mod import;
 pub mod parse;
 pub mod types;
+pub mod utils;
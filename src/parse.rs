@@ -1,18 +1,81 @@
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
 
-use ordered_float::OrderedFloat;
+use chrono::NaiveDateTime;
 
 use crate::core;
-use crate::core::line::LineParser;
+use crate::core::line::{self, LineParser};
+use crate::types::absolute_time::AbsoluteTime;
 use crate::types::errors::AscParseError;
-use crate::types::frame::{Direction, Frame, FrameType};
-use crate::types::keys::FrameKey;
 use crate::types::log::Log;
+use crate::types::parse_options::{ParseOptions, ProgressMode};
+use crate::types::trigger::TriggerBlock;
+
+/// Highest major version of the `version` header line (`version 8.0.0` or
+/// `// version 8.0.0`) accepted without `ParseOptions::ignore_version`.
+pub const SUPPORTED_ASC_MAJOR_VERSION: u32 = 8;
+
+/// Number of lines between two progress reports.
+const PROGRESS_INTERVAL_LINES: u64 = 1000;
+
+/// Maximum number of bytes of an unreadable line quoted in `Log::parse_warnings`.
+const WARNING_SNIPPET_LEN: usize = 40;
 
 /// Parses a Vector ASCII trace (`.asc`) file and builds a `Log`.
 pub fn from_asc_file(path: &str, log: &mut Log) -> Result<(), AscParseError> {
+    from_asc_file_with_options(path, log, &ParseOptions::default())
+}
+
+/// Same as `from_asc_file`, with explicit `ParseOptions`.
+pub fn from_asc_file_with_options(
+    path: &str,
+    log: &mut Log,
+    options: &ParseOptions,
+) -> Result<(), AscParseError> {
+    read_asc_file(path, log, options, &mut |_, _| {})?;
+    core::sort::rebuild_views(log);
+    Ok(())
+}
+
+/// Same as `from_asc_file_with_options`, calling `progress(done, total)` every
+/// 1000 lines and once at the end of the file.
+///
+/// `done` and `total` are bytes or lines depending on `ParseOptions::progress_mode`
+/// and `ParseOptions::estimate_total_lines`. The estimated total of
+/// `ProgressMode::LinesEstimated` is refined at every report and becomes exact
+/// in the final one.
+pub fn from_asc_file_with_progress<F>(
+    path: &str,
+    log: &mut Log,
+    options: &ParseOptions,
+    mut progress: F,
+) -> Result<(), AscParseError>
+where
+    F: FnMut(u64, u64),
+{
+    read_asc_file(path, log, options, &mut progress)?;
+    core::sort::rebuild_views(log);
+    Ok(())
+}
+
+/// Same as `from_asc_file`, but the sorted views are built concurrently with rayon.
+///
+/// The parse pass itself stays sequential, only the sort step is parallelised.
+#[cfg(feature = "rayon")]
+pub fn from_asc_file_parallel(path: &str, log: &mut Log) -> Result<(), AscParseError> {
+    read_asc_file(path, log, &ParseOptions::default(), &mut |_, _| {})?;
+    core::sort::rebuild_views_parallel(log);
+    Ok(())
+}
+
+/// Reads the `.asc` file line by line and fills `log.frames` and `frame_by_file_order`.
+fn read_asc_file(
+    path: &str,
+    log: &mut Log,
+    options: &ParseOptions,
+    progress: &mut dyn FnMut(u64, u64),
+) -> Result<(), AscParseError> {
     // clear frames
     log.clear_frames();
 
@@ -27,8 +90,8 @@ pub fn from_asc_file(path: &str, log: &mut Log) -> Result<(), AscParseError> {
     let mut found_abs_time: bool = false;
 
     let path_owned: String = path.to_string();
-    let mut reader: BufReader<File> = match File::open(path) {
-        Ok(file) => BufReader::new(file),
+    let file: File = match File::open(path) {
+        Ok(file) => file,
         Err(source) => {
             return Err(AscParseError::OpenFile {
                 path: path_owned.clone(),
@@ -37,258 +100,539 @@ pub fn from_asc_file(path: &str, log: &mut Log) -> Result<(), AscParseError> {
         }
     };
 
-    let mut line_parser: LineParser = LineParser::new();
+    // reject empty files unless explicitly allowed
+    let file_len: u64 = file
+        .metadata()
+        .map_err(|source| AscParseError::Read {
+            path: path_owned.clone(),
+            source,
+        })?
+        .len();
+    if file_len == 0 && !options.allow_empty_file {
+        return Err(AscParseError::FileEmpty { path: path_owned });
+    }
 
-    // read .asc file line by line reusing the same buffer
-    let mut line: String = String::new();
-    loop {
-        line.clear();
-        let bytes_read = reader
-            .read_line(&mut line)
-            .map_err(|source| AscParseError::Read {
+    // a single first pass serves both the pre-scan and the line count
+    let count_lines_first: bool = options.estimate_total_lines.is_none()
+        && options.progress_mode == ProgressMode::LinesTwoPass;
+    let scan: Option<FileScan> = if options.prescan_for_capacity || count_lines_first {
+        Some(
+            scan_file(path, options).map_err(|source| AscParseError::Read {
                 path: path_owned.clone(),
                 source,
-            })?;
-        if bytes_read == 0 {
-            break;
+            })?,
+        )
+    } else {
+        None
+    };
+
+    if options.prescan_for_capacity
+        && let Some(scan) = &scan
+    {
+        for info in log.channel_map.values_mut() {
+            info.estimated_frame_count = scan.frame_counts.get(&info.number).copied().unwrap_or(0);
+        }
+        let total: usize = log
+            .channel_map
+            .values()
+            .map(|info| info.estimated_frame_count)
+            .sum();
+        log.frames.reserve(total);
+        log.frame_by_file_order.reserve(total);
+    }
+
+    let total_lines: Option<u64> = match (options.estimate_total_lines, &scan) {
+        (Some(lines), _) => Some(lines as u64),
+        (None, Some(scan)) if count_lines_first => Some(scan.lines),
+        _ => None,
+    };
+    let mut tracker: ProgressTracker = ProgressTracker {
+        mode: options.progress_mode,
+        file_len,
+        total_lines,
+        bytes: 0,
+        lines: 0,
+    };
+
+    let mut reader: BufReader<File> = BufReader::new(file);
+
+    let mut line_parser: LineParser = LineParser::with_options(options);
+    let mut in_trigger_block: bool = false;
+
+    // read .asc file line by line reusing the same buffer
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        buf.clear();
+        let line: Result<&str, io::Error> = match reader.read_until(b'\n', &mut buf) {
+            Ok(0) => break,
+            Ok(_) => std::str::from_utf8(&buf)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            Err(err) => Err(err),
+        };
+        let line: &str = match line {
+            Ok(line) => line,
+            Err(source) if options.allow_truncated => {
+                let snippet_len: usize = buf.len().min(WARNING_SNIPPET_LEN);
+                log.parse_warnings.push(format!(
+                    "line {}: {} ({:?}), parsing stopped",
+                    tracker.lines + 1,
+                    source,
+                    String::from_utf8_lossy(&buf[..snippet_len]).trim_end(),
+                ));
+                break;
+            }
+            Err(source) => {
+                return Err(AscParseError::Read {
+                    path: path_owned,
+                    source,
+                });
+            }
+        };
+        tracker.bytes += buf.len() as u64;
+        tracker.lines += 1;
+        if tracker.lines % PROGRESS_INTERVAL_LINES == 0 {
+            let (done, total) = tracker.current();
+            progress(done, total);
         }
         let trimmed = line.trim_end_matches(['\n', '\r']);
-        if !found_abs_time && let Some(time) = core::abs_time::from_line(trimmed) {
-            log.absolute_time = time;
-            found_abs_time = true;
-            continue; // skip abs_time check for rest of the line
+        if let Some(time) = core::abs_time::from_line(trimmed) {
+            if in_trigger_block && let Some(block) = log.trigger_blocks.last_mut() {
+                block.start_timestamp = block_offset(log.absolute_time.value, time.value);
+                block.absolute_time = time.clone();
+                line_parser.set_time_offset(block.start_timestamp);
+            }
+            if !found_abs_time {
+                log.absolute_time = time;
+                found_abs_time = true;
+            }
+            line_parser.skip_line();
+            continue;
+        }
+        if let Some(date) = triggerblock_line(trimmed, "Begin") {
+            let absolute_time: AbsoluteTime =
+                core::abs_time::from_date_text(date).unwrap_or_else(|| log.absolute_time.clone());
+            if !found_abs_time && absolute_time.value.is_some() {
+                log.absolute_time = absolute_time.clone();
+                found_abs_time = true;
+            }
+            let start_timestamp: f64 = block_offset(log.absolute_time.value, absolute_time.value);
+            log.trigger_blocks.push(TriggerBlock {
+                start_timestamp,
+                absolute_time,
+                frame_keys: Vec::new(),
+            });
+            // signal samples and triggers of the block are recorded at block-adjusted time
+            line_parser.set_time_offset(start_timestamp);
+            in_trigger_block = true;
+            line_parser.skip_line();
+            continue;
         }
+        if triggerblock_line(trimmed, "End").is_some() {
+            in_trigger_block = false;
+            line_parser.set_time_offset(0.0);
+            line_parser.skip_line();
+            continue;
+        }
+        if let Some(version) = version_line(trimmed) {
+            if log.asc_version.is_none() {
+                let version: String = version.trim().to_string();
+                let major: Option<u32> = version.split('.').next().and_then(|m| m.parse().ok());
+                if !options.ignore_version && major.is_some_and(|m| m > SUPPORTED_ASC_MAJOR_VERSION)
+                {
+                    return Err(AscParseError::UnsupportedVersion { version });
+                }
+                log.asc_version = Some(version);
+            }
+            line_parser.skip_line();
+            continue;
+        }
+        let frames_before: usize = log.frame_by_file_order.len();
         line_parser.parse(trimmed, log);
+        if in_trigger_block && let Some(block_index) = log.trigger_blocks.len().checked_sub(1) {
+            let block: &mut TriggerBlock = &mut log.trigger_blocks[block_index];
+            let new_keys = &log.frame_by_file_order[frames_before..];
+            block.frame_keys.extend_from_slice(new_keys);
+            // frames of a block with its own date are dated from the block start
+            let block_start: Option<NaiveDateTime> = block
+                .absolute_time
+                .value
+                .filter(|_| block.absolute_time.value != log.absolute_time.value);
+            for key in new_keys {
+                if let Some(frame) = log.frames.get_mut(*key) {
+                    frame.trigger_block = Some(block_index);
+                    if block_start.is_some() {
+                        frame.absolute_time =
+                            line::frame_absolute_time(block_start, frame.timestamp);
+                    }
+                }
+            }
+        }
     }
 
-    // ---- Sorting ---- //
-    let base_keys: &[FrameKey] = log.frame_by_file_order.as_slice();
-    let order_index: HashMap<FrameKey, usize> = base_keys
-        .iter()
-        .enumerate()
-        .map(|(idx, key)| (*key, idx))
-        .collect();
-    let frames = &log.frames;
-    let channel_map = &log.channel_map;
-    let fallback_index =
-        |key: FrameKey| -> usize { order_index.get(&key).copied().unwrap_or(usize::MAX) };
-    let refill = |target: &mut Vec<FrameKey>, source: &[FrameKey]| {
-        target.clear();
-        target.extend_from_slice(source);
-    };
+    let (done, total) = tracker.finished();
+    progress(done, total);
+    Ok(())
+}
+
+/// Return the version of a `version 8.0.0` header line, also written by Vector
+/// tools as the comment `// version 8.0.0`.
+fn version_line(line: &str) -> Option<&str> {
+    let line: &str = line.trim_start();
+    let line: &str = line.strip_prefix("//").map_or(line, str::trim_start);
+    line.strip_prefix("version ").filter(|version| {
+        version
+            .trim_start()
+            .starts_with(|c: char| c.is_ascii_digit())
+    })
+}
 
-    let mut last_by_id_channel: HashMap<(u32, u8), FrameKey> = HashMap::new();
-    for &key in base_keys {
-        if let Some(frame) = frames.get(key)
-            && frame.ftype == FrameType::Can
-        {
-            last_by_id_channel.insert((frame.id, frame.channel), key);
+/// Return the text after `<keyword> Triggerblock` if `line` is such a line.
+fn triggerblock_line<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest: &str = line.trim_start().strip_prefix(keyword)?.trim_start();
+    let (tag, date) = rest.split_at_checked("Triggerblock".len())?;
+    tag.eq_ignore_ascii_case("Triggerblock")
+        .then_some(date.trim())
+}
+
+/// Seconds from `file_start` to `block_start`, 0.0 when either is unknown.
+fn block_offset(file_start: Option<NaiveDateTime>, block_start: Option<NaiveDateTime>) -> f64 {
+    match (file_start, block_start) {
+        (Some(file_start), Some(block_start)) => {
+            (block_start - file_start).num_milliseconds() as f64 / 1000.0
         }
+        _ => 0.0,
     }
-    let mut id_chn_keys: Vec<FrameKey> = last_by_id_channel.values().copied().collect();
-    id_chn_keys.sort_by_key(|key| fallback_index(*key));
-
-    let direction_rank = |dir: &Direction| match dir {
-        Direction::Rx => 0_u8,
-        Direction::Tx => 1_u8,
-    };
+}
 
-    let protocol_rank = |frame: &Frame| -> u8 { if frame.byte_length <= 8 { 0 } else { 1 } };
+/// Progress state of `read_asc_file`.
+struct ProgressTracker {
+    mode: ProgressMode,
+    file_len: u64,
+    /// Total lines, either given by the caller or counted by the first pass.
+    total_lines: Option<u64>,
+    bytes: u64,
+    lines: u64,
+}
 
-    let sort_by_timestamp = |vec: &mut Vec<FrameKey>| {
-        vec.sort_by_key(|key| {
-            let key = *key;
-            let fallback = fallback_index(key);
-            match frames.get(key) {
-                Some(frame) => (0_u8, OrderedFloat(frame.timestamp), fallback),
-                None => (1_u8, OrderedFloat(0.0), fallback),
+impl ProgressTracker {
+    fn current(&self) -> (u64, u64) {
+        if let Some(total) = self.total_lines {
+            return (self.lines, total.max(self.lines));
+        }
+        match self.mode {
+            ProgressMode::LinesEstimated if self.bytes > 0 => {
+                // average line length of the lines read so far
+                let estimate: u64 = self.file_len.saturating_mul(self.lines) / self.bytes;
+                (self.lines, estimate.max(self.lines))
             }
-        });
-    };
+            _ => (self.bytes, self.file_len.max(self.bytes)),
+        }
+    }
 
-    let sort_by_channel = |vec: &mut Vec<FrameKey>| {
-        vec.sort_by_key(|key| {
-            let key = *key;
-            let fallback = fallback_index(key);
-            match frames.get(key) {
-                Some(frame) => (0_u8, frame.channel, OrderedFloat(frame.timestamp), fallback),
-                None => (1_u8, u8::MAX, OrderedFloat(0.0), fallback),
-            }
-        });
-    };
+    fn finished(&self) -> (u64, u64) {
+        match (self.total_lines, self.mode) {
+            (None, ProgressMode::Bytes) => (self.bytes, self.bytes),
+            _ => (self.lines, self.lines),
+        }
+    }
+}
 
-    let sort_by_direction = |vec: &mut Vec<FrameKey>| {
-        vec.sort_by_key(|key| {
-            let key = *key;
-            let fallback = fallback_index(key);
-            match frames.get(key) {
-                Some(frame) => (
-                    0_u8,
-                    direction_rank(&frame.direction),
-                    OrderedFloat(frame.timestamp),
-                    fallback,
-                ),
-                None => (1_u8, u8::MAX, OrderedFloat(0.0), fallback),
-            }
-        });
-    };
+/// Result of the first pass of `read_asc_file`, see `scan_file`.
+struct FileScan {
+    /// Lines of the file, a last line without terminator included.
+    lines: u64,
+    /// Frame lines per channel, only filled with `ParseOptions::prescan_for_capacity`.
+    frame_counts: HashMap<u8, usize>,
+}
 
-    let sort_by_can_msg_name = |vec: &mut Vec<FrameKey>| {
-        vec.sort_by_key(|key| {
-            let key = *key;
-            let fallback = fallback_index(key);
-            let (rank, name) = frames
-                .get(key)
-                .and_then(|frame| {
-                    channel_map
-                        .get(&frame.channel)
-                        .and_then(|info| info.database.as_ref())
-                        .and_then(|db| db.get_message_by_key(frame.msg_key))
-                        .map(|msg| msg.name.as_str())
-                })
-                .map(|name| (0_u8, name))
-                .unwrap_or((1_u8, ""));
-            (rank, name, fallback)
-        });
+/// Count the lines of `path` and, with `ParseOptions::prescan_for_capacity`, the
+/// frame lines per channel the parser keeps: lines made of an optional timestamp,
+/// an optional `CANFD` and a channel number, outside `channel_filter` and, with
+/// `skip_disturbance_blocks`, outside disturbance blocks.
+fn scan_file(path: &str, options: &ParseOptions) -> io::Result<FileScan> {
+    let mut reader: BufReader<File> = BufReader::new(File::open(path)?);
+    let mut scan: FileScan = FileScan {
+        lines: 0,
+        frame_counts: HashMap::new(),
     };
+    if !options.prescan_for_capacity {
+        scan.lines = count_lines(&mut reader)?;
+        return Ok(scan);
+    }
 
-    let sort_by_can_msg_id = |vec: &mut Vec<FrameKey>| {
-        vec.sort_by_key(|key| {
-            let key = *key;
-            let fallback = fallback_index(key);
-            match frames.get(key) {
-                Some(frame) => (0_u8, frame.id, fallback),
-                None => (1_u8, u32::MAX, fallback),
+    let skip_disturbed: bool = options.skip_disturbance_blocks && !options.tag_disturbed_frames;
+    let mut in_disturbance_block: bool = false;
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        scan.lines += 1;
+
+        let mut it = buf
+            .split(|b| b.is_ascii_whitespace())
+            .filter(|t| !t.is_empty());
+        let mut tok: Option<&[u8]> = it.next();
+        if tok.is_some_and(|tok| std::str::from_utf8(tok).is_ok_and(|t| t.parse::<f64>().is_ok())) {
+            tok = it.next();
+        }
+        if tok == Some(b"DISTURBANCE".as_slice()) {
+            match it.next() {
+                Some(flag) if flag.eq_ignore_ascii_case(b"begin") => in_disturbance_block = true,
+                Some(flag) if flag.eq_ignore_ascii_case(b"end") => in_disturbance_block = false,
+                _ => {}
             }
-        });
-    };
+            continue;
+        }
+        if tok == Some(b"CANFD".as_slice()) {
+            tok = it.next();
+        }
+        let Some(channel) = tok
+            .and_then(|tok| std::str::from_utf8(tok).ok())
+            .and_then(|tok| tok.parse::<u8>().ok())
+        else {
+            continue;
+        };
+        let filtered_out: bool = options
+            .channel_filter
+            .as_ref()
+            .is_some_and(|filter| !filter.contains(&channel));
+        if filtered_out || (in_disturbance_block && skip_disturbed) {
+            continue;
+        }
+        *scan.frame_counts.entry(channel).or_insert(0) += 1;
+    }
+    Ok(scan)
+}
 
-    let sort_by_can_dlc = |vec: &mut Vec<FrameKey>| {
-        vec.sort_by_key(|key| {
-            let key = *key;
-            let fallback = fallback_index(key);
-            match frames.get(key) {
-                Some(frame) => (0_u8, frame.byte_length, fallback),
-                None => (1_u8, u16::MAX, fallback),
-            }
-        });
-    };
+/// Count the lines of `reader` by scanning for `'\n'`, a last line without
+/// terminator included.
+fn count_lines(reader: &mut impl BufRead) -> io::Result<u64> {
+    let mut lines: u64 = 0;
+    let mut last_byte: Option<u8> = None;
+    loop {
+        let buf: &[u8] = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        lines += buf.iter().filter(|&&byte| byte == b'\n').count() as u64;
+        last_byte = buf.last().copied();
+        let len: usize = buf.len();
+        reader.consume(len);
+    }
+    if last_byte.is_some_and(|byte| byte != b'\n') {
+        lines += 1;
+    }
+    Ok(lines)
+}
 
-    let sort_by_can_protocol = |vec: &mut Vec<FrameKey>| {
-        vec.sort_by_key(|key| {
-            let key = *key;
-            let fallback = fallback_index(key);
-            match frames.get(key) {
-                Some(frame) => (0_u8, protocol_rank(frame), frame.byte_length, fallback),
-                None => (1_u8, u8::MAX, u16::MAX, fallback),
-            }
-        });
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::frame::FrameType;
+
+    const TWO_TRIGGER_BLOCKS: &str = "\
+date Thu Nov 23 10:30:00.000 am 2023
+base hex  timestamps absolute
+Begin Triggerblock Thu Nov 23 10:30:00.000 am 2023
+   0.100000 1 100 Rx d 1 01
+   0.150000 TRIGGER \"first\"
+   0.200000 1 100 Rx d 1 02
+End TriggerBlock
+Begin Triggerblock Thu Nov 23 10:31:00.000 am 2023
+   0.050000 1 100 Rx d 1 03
+   0.100000 TRIGGER \"second\"
+   0.150000 1 100 Rx d 1 04
+End TriggerBlock
+";
+
+    /// Parse `content` as a `.asc` file with a CAN channel 1 without database.
+    fn parse_asc(name: &str, content: &str, options: &ParseOptions) -> Log {
+        try_parse_asc(name, content, options).unwrap()
+    }
 
-    let sort_by_can_sender_node = |vec: &mut Vec<FrameKey>| {
-        vec.sort_by_key(|key| {
-            let key = *key;
-            let fallback = fallback_index(key);
-            let (rank, name) = frames
-                .get(key)
-                .and_then(|frame| {
-                    channel_map
-                        .get(&frame.channel)
-                        .and_then(|info| info.database.as_ref())
-                        .and_then(|db| db.get_node_by_key(frame.tx_node_key))
-                        .map(|node| node.name.as_str())
-                })
-                .map(|name| (0_u8, name))
-                .unwrap_or((1_u8, ""));
-            (rank, name, fallback)
-        });
-    };
+    fn try_parse_asc(
+        name: &str,
+        content: &str,
+        options: &ParseOptions,
+    ) -> Result<Log, AscParseError> {
+        let path: String = std::env::temp_dir()
+            .join(format!(
+                "trace_analyzer_{}_{}.asc",
+                name,
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .into_owned();
+        std::fs::write(&path, content).unwrap();
+        let mut log: Log = Log::default();
+        log.add_can_channel(1, None);
+        let result: Result<(), AscParseError> =
+            from_asc_file_with_options(&path, &mut log, options);
+        std::fs::remove_file(&path).ok();
+        result.map(|_| log)
+    }
 
-    let sort_by_can_data = |vec: &mut Vec<FrameKey>| {
-        vec.sort_by_key(|key| {
-            let key = *key;
-            let fallback = fallback_index(key);
-            match frames.get(key) {
-                Some(frame) => (0_u8, frame.data.as_str(), fallback),
-                None => (1_u8, "", fallback),
-            }
-        });
-    };
+    fn payloads(log: &Log, view: &[crate::types::keys::FrameKey]) -> Vec<String> {
+        view.iter()
+            .map(|key| log.frames[*key].data.clone())
+            .collect()
+    }
 
-    let sort_by_can_comment = |vec: &mut Vec<FrameKey>| {
-        vec.sort_by_key(|key| {
-            let key = *key;
-            let fallback = fallback_index(key);
-            let (rank, comment) = frames
-                .get(key)
-                .and_then(|frame| {
-                    channel_map
-                        .get(&frame.channel)
-                        .and_then(|info| info.database.as_ref())
-                        .and_then(|db| db.get_message_by_key(frame.msg_key))
-                        .map(|msg| msg.comment.as_str())
-                })
-                .map(|comment| (0_u8, comment))
-                .unwrap_or((1_u8, ""));
-            (rank, comment, fallback)
-        });
-    };
+    #[test]
+    fn trigger_blocks_keep_their_frames_apart() {
+        let log: Log = parse_asc("two_blocks", TWO_TRIGGER_BLOCKS, &ParseOptions::default());
+
+        assert_eq!(log.trigger_blocks.len(), 2);
+        assert_eq!(log.trigger_blocks[0].start_timestamp, 0.0);
+        assert_eq!(log.trigger_blocks[1].start_timestamp, 60.0);
+        assert_eq!(
+            payloads(&log, &log.trigger_blocks[0].frame_keys),
+            ["01", "02"]
+        );
+        assert_eq!(
+            payloads(&log, &log.trigger_blocks[1].frame_keys),
+            ["03", "04"]
+        );
+
+        // relative timestamps restart in every block, time ordered views do not
+        assert_eq!(
+            payloads(&log, &log.frame_by_timestamp),
+            ["01", "02", "03", "04"]
+        );
+        assert_eq!(
+            payloads(&log, &log.frame_by_block_timestamp),
+            ["01", "02", "03", "04"]
+        );
+        let times: Vec<f64> = log
+            .frame_by_timestamp
+            .iter()
+            .map(|key| log.block_adjusted_timestamp(&log.frames[*key]))
+            .collect();
+        assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!((times[2] - 60.05).abs() < 1e-9);
+
+        let third = &log.frames[log.trigger_blocks[1].frame_keys[0]];
+        assert_eq!(third.timestamp, 0.05);
+        assert_eq!(third.trigger_block, Some(1));
+        assert_eq!(third.absolute_time, "2023-11-23 10:31:00.050");
+
+        // triggers, like signal samples, are recorded at block-adjusted time
+        let names: Vec<&str> = log.triggers.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["first", "second"]);
+        assert!((log.triggers[1].timestamp - 60.1).abs() < 1e-9);
+        assert_eq!(log.triggers_in_range(60.0, 61.0)[0].name, "second");
+    }
 
-    refill(&mut log.frame_by_timestamp, base_keys);
-    sort_by_timestamp(&mut log.frame_by_timestamp);
-    refill(&mut log.id_chn_by_timestamp, &id_chn_keys);
-    sort_by_timestamp(&mut log.id_chn_by_timestamp);
-
-    refill(&mut log.frame_by_channel, base_keys);
-    sort_by_channel(&mut log.frame_by_channel);
-    refill(&mut log.id_chn_by_channel, &id_chn_keys);
-    sort_by_channel(&mut log.id_chn_by_channel);
-
-    refill(&mut log.frame_by_direction, base_keys);
-    sort_by_direction(&mut log.frame_by_direction);
-    refill(&mut log.id_chn_by_direction, &id_chn_keys);
-    sort_by_direction(&mut log.id_chn_by_direction);
-
-    let can_keys: Vec<FrameKey> = base_keys
-        .iter()
-        .copied()
-        .filter(|key| matches!(frames.get(*key), Some(frame) if frame.ftype == FrameType::Can))
-        .collect();
-
-    refill(&mut log.frame_by_can_msg_name, &can_keys);
-    sort_by_can_msg_name(&mut log.frame_by_can_msg_name);
-    refill(&mut log.id_chn_by_can_msg_name, &id_chn_keys);
-    sort_by_can_msg_name(&mut log.id_chn_by_can_msg_name);
-
-    refill(&mut log.frame_by_can_msg_id, &can_keys);
-    sort_by_can_msg_id(&mut log.frame_by_can_msg_id);
-    refill(&mut log.id_chn_by_can_msg_id, &id_chn_keys);
-    sort_by_can_msg_id(&mut log.id_chn_by_can_msg_id);
-
-    refill(&mut log.frame_by_can_dlc, &can_keys);
-    sort_by_can_dlc(&mut log.frame_by_can_dlc);
-    refill(&mut log.id_chn_by_can_dlc, &id_chn_keys);
-    sort_by_can_dlc(&mut log.id_chn_by_can_dlc);
-
-    refill(&mut log.frame_by_can_protocol, &can_keys);
-    sort_by_can_protocol(&mut log.frame_by_can_protocol);
-    refill(&mut log.id_chn_by_can_protocol, &id_chn_keys);
-    sort_by_can_protocol(&mut log.id_chn_by_can_protocol);
-
-    refill(&mut log.frame_by_can_sender_node, &can_keys);
-    sort_by_can_sender_node(&mut log.frame_by_can_sender_node);
-    refill(&mut log.id_chn_by_can_sender_node, &id_chn_keys);
-    sort_by_can_sender_node(&mut log.id_chn_by_can_sender_node);
-
-    refill(&mut log.frame_by_can_data, &can_keys);
-    sort_by_can_data(&mut log.frame_by_can_data);
-    refill(&mut log.id_chn_by_can_data, &id_chn_keys);
-    sort_by_can_data(&mut log.id_chn_by_can_data);
-
-    refill(&mut log.frame_by_can_comment, &can_keys);
-    sort_by_can_comment(&mut log.frame_by_can_comment);
-    refill(&mut log.id_chn_by_can_comment, &id_chn_keys);
-    sort_by_can_comment(&mut log.id_chn_by_can_comment);
+    #[test]
+    fn version_header_in_both_forms() {
+        for header in ["version 8.0.0", "// version 8.0.0"] {
+            let content: String = format!("{}\n   0.100000 1 100 Rx d 1 01\n", header);
+            let log: Log = parse_asc("version", &content, &ParseOptions::default());
+            assert_eq!(log.asc_version.as_deref(), Some("8.0.0"));
+            assert!(log.comments.is_empty());
+        }
 
-    Ok(())
+        let log: Log = parse_asc(
+            "version_text",
+            "// version notes\n",
+            &ParseOptions::default(),
+        );
+        assert_eq!(log.asc_version, None);
+        assert_eq!(log.comments.len(), 1);
+    }
+
+    #[test]
+    fn unsupported_version_unless_ignored() {
+        for header in ["version 9.1.0", "// version 9.1.0"] {
+            let content: String = format!("{}\n", header);
+            let result: Result<Log, AscParseError> =
+                try_parse_asc("version_9", &content, &ParseOptions::default());
+            assert!(matches!(
+                result,
+                Err(AscParseError::UnsupportedVersion { version }) if version == "9.1.0"
+            ));
+
+            let options: ParseOptions = ParseOptions {
+                ignore_version: true,
+                ..ParseOptions::default()
+            };
+            let log: Log = parse_asc("version_9_ignored", &content, &options);
+            assert_eq!(log.asc_version.as_deref(), Some("9.1.0"));
+        }
+    }
+
+    #[test]
+    fn error_frames_count_in_the_bit_error_rate() {
+        let content: &str = "\
+   0.100000 1 100 Rx d 8 00 00 00 00 00 00 00 00
+   0.200000 1 ErrorFrame
+   0.300000 1 100 Rx d 8 00 00 00 00 00 00 00 00
+";
+        let log: Log = parse_asc("error_frame", content, &ParseOptions::default());
+
+        let error_frame = &log.frames[log.frame_by_file_order[1]];
+        assert_eq!(error_frame.ftype, FrameType::ErrorFrame);
+        assert_eq!(error_frame.channel, 1);
+        assert_eq!(error_frame.timestamp, 0.2);
+        // one error over two standard frames of 47 + 64 bits
+        assert_eq!(log.compute_bit_error_rate(1), Some(1.0 / 222.0));
+    }
+
+    #[test]
+    fn prescan_counts_the_frames_kept() {
+        let content: &str = "\
+date Thu Nov 23 10:30:00.000 am 2023
+   0.100000 1 100 Rx d 1 01
+   0.200000 2 100 Rx d 1 02
+   0.250000 DISTURBANCE begin
+   0.300000 1 100 Rx d 1 03
+   0.350000 DISTURBANCE end
+   0.400000 CANFD 1 200 Rx BrsOn EsiValid d 1 04
+";
+        let options: ParseOptions = ParseOptions {
+            channel_filter: Some(vec![1]),
+            prescan_for_capacity: true,
+            progress_mode: ProgressMode::LinesTwoPass,
+            ..ParseOptions::default()
+        };
+        let path: String = std::env::temp_dir()
+            .join(format!("trace_analyzer_prescan_{}.asc", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        std::fs::write(&path, content).unwrap();
+        let mut log: Log = Log::default();
+        log.add_can_channel(1, None).add_can_channel(2, None);
+        let result: Result<(), AscParseError> =
+            from_asc_file_with_options(&path, &mut log, &options);
+        let scan: io::Result<FileScan> = scan_file(&path, &options);
+        std::fs::remove_file(&path).ok();
+        result.unwrap();
+
+        assert_eq!(payloads(&log, &log.frame_by_file_order), ["01", "04"]);
+        assert_eq!(log.channel_map[&1].estimated_frame_count, 2);
+        assert_eq!(log.channel_map[&2].estimated_frame_count, 0);
+        assert_eq!(scan.unwrap().lines, 7);
+    }
+
+    #[test]
+    fn absolute_range_spans_trigger_blocks() {
+        let log: Log = parse_asc("abs_range", TWO_TRIGGER_BLOCKS, &ParseOptions::default());
+        let range = |start: &str, end: &str| {
+            payloads(
+                &log,
+                log.frame_by_absolute_timestamp_range(start, end).unwrap(),
+            )
+        };
+        assert_eq!(
+            range("2023-11-23 10:31:00.000", "2023-11-23 10:31:00.100"),
+            ["03"]
+        );
+        assert_eq!(
+            range("2023-11-23 10:30:00.150", "2023-11-23 10:31:00.050"),
+            ["02", "03"]
+        );
+        assert!(range("2023-11-23 10:30:01", "2023-11-23 10:30:59").is_empty());
+    }
 }
@@ -1,4 +1,4 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime};
 
 /// Represents an absolute, timezone-unaware timestamp.
 ///
@@ -17,6 +17,7 @@ use chrono::NaiveDateTime;
 ///   `None` if not available.
 ///
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
 pub struct AbsoluteTime {
     pub text: String,
     pub value: Option<NaiveDateTime>,
@@ -31,4 +32,27 @@ impl AbsoluteTime {
         self.text.clear();
         self.value = None;
     }
+
+    /// Milliseconds since the Unix epoch, reading the naive timestamp as UTC.
+    /// Returns `None` if no timestamp is available.
+    pub fn to_unix_timestamp_ms(&self) -> Option<i64> {
+        self.value.map(|dt| dt.and_utc().timestamp_millis())
+    }
+
+    /// Builds an `AbsoluteTime` from milliseconds since the Unix epoch (UTC).
+    ///
+    /// `text` is set to the ISO 8601 representation (`"2025-08-05T19:23:45.123"`).
+    /// An out-of-range value yields `AbsoluteTime::default()`.
+    pub fn from_unix_timestamp_ms(ms: i64) -> AbsoluteTime {
+        match DateTime::from_timestamp_millis(ms) {
+            Some(dt) => {
+                let value: NaiveDateTime = dt.naive_utc();
+                AbsoluteTime {
+                    text: value.format("%Y-%m-%dT%H:%M:%S%.3f").to_string(),
+                    value: Some(value),
+                }
+            }
+            None => AbsoluteTime::default(),
+        }
+    }
 }
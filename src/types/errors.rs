@@ -1,3 +1,4 @@
+use dbc_editor::types::database::SignalKey;
 use std::io;
 use thiserror::Error;
 
@@ -18,4 +19,128 @@ pub enum AscParseError {
         #[source]
         source: io::Error,
     },
+    #[error("File is empty: {path}")]
+    FileEmpty { path: String },
+    #[error("Unsupported .asc format version {version}")]
+    UnsupportedVersion { version: String },
+}
+
+/// Errors produced while exporting a `Log` to CSV.
+#[derive(Debug, Error)]
+pub enum CsvExportError {
+    #[error("Failed while writing CSV: {0}")]
+    Write(#[from] io::Error),
+}
+
+/// Errors produced while importing a `Log` from CSV.
+#[derive(Debug, Error)]
+pub enum CsvImportError {
+    #[error("Missing or malformed CSV header, 'timestamp' and 'id' columns are required")]
+    MalformedHeader,
+    #[error("Malformed field '{field}' at line {line}")]
+    MalformedRow { line: usize, field: String },
+    #[error("Invalid UTF-8 at line {line}")]
+    Utf8Error { line: usize },
+    #[error("Failed while reading CSV: {0}")]
+    Read(#[from] io::Error),
+}
+
+/// Errors produced while importing a SocketCAN `candump -l` log.
+#[derive(Debug, Error)]
+pub enum SocketCanImportError {
+    #[error("Malformed timestamp at line {line}, expected '(seconds.micros)'")]
+    MalformedTimestamp { line: usize },
+    #[error("Malformed interface name at line {line}, expected e.g. 'vcan0'")]
+    MalformedInterface { line: usize },
+    #[error("Malformed frame at line {line}, expected 'id#data'")]
+    MalformedFrame { line: usize },
+    #[error("Failed while reading the log: {0}")]
+    Read(#[from] io::Error),
+}
+
+/// Errors produced while exporting signals to a gnuplot script.
+#[derive(Debug, Error)]
+pub enum GnuplotExportError {
+    #[error("No signal to plot")]
+    NoSignals,
+    #[error("Signal {0:?} not found in any channel database")]
+    UnknownSignal(SignalKey),
+    #[error("Failed while writing the gnuplot script: {0}")]
+    Write(#[from] io::Error),
+}
+
+/// Errors produced while changing the scaling of a decoded signal.
+#[derive(Debug, Error)]
+pub enum SignalScaleError {
+    #[error("Signal {0:?} not found in any channel database")]
+    SignalNotFound(SignalKey),
+}
+
+/// Errors produced while parsing user supplied values.
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Invalid timestamp '{value}', expected 'YYYY-MM-DD HH:MM:SS.mmm'")]
+    InvalidTimestampFormat { value: String },
+}
+
+/// Errors produced while exporting a signal to a `.wav` file.
+#[cfg(feature = "wav")]
+#[derive(Debug, Error)]
+pub enum WavExportError {
+    #[error("Signal '{name}' not found or empty on channel {channel}")]
+    UnknownSignal { name: String, channel: u8 },
+    #[error("Sample rate must be greater than 0")]
+    InvalidSampleRate,
+    #[error("Failed to write the wav file: {0}")]
+    Hound(#[from] hound::Error),
+}
+
+/// Errors produced while exporting signals to an `.xlsx` file.
+#[cfg(feature = "xlsx")]
+#[derive(Debug, Error)]
+pub enum XlsxExportError {
+    #[error("Resampling interval must be greater than 0")]
+    InvalidInterval,
+    #[error("Failed to write the xlsx file: {0}")]
+    Xlsx(#[from] rust_xlsxwriter::XlsxError),
+}
+
+/// Errors produced while saving a `Log` checkpoint (`.tca` file).
+#[cfg(feature = "bincode")]
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("Not a valid .tca file: {path}")]
+    InvalidExtension { path: String },
+    #[error("Failed to encode the log: {0}")]
+    Encode(#[from] bincode::error::EncodeError),
+    #[error("Failed while writing '{path}': {source}")]
+    Write {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Errors produced while loading a `Log` checkpoint (`.tca` file).
+#[cfg(feature = "bincode")]
+#[derive(Debug, Error)]
+pub enum LoadError {
+    #[error("Not a valid .tca file: {path}")]
+    InvalidExtension { path: String },
+    #[error("Failed while reading '{path}': {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("'{path}' is not a trace_analyzer checkpoint (wrong magic bytes)")]
+    WrongMagic { path: String },
+    #[error("'{path}' uses the unsupported checkpoint format version {version}")]
+    UnsupportedVersion { path: String, version: u8 },
+    #[error("'{path}' ends with an incomplete checkpoint record")]
+    Truncated { path: String },
+    #[error("'{path}' has an incremental record that does not follow the previous ones")]
+    RecordOutOfSequence { path: String },
+    #[error("Failed to decode the log: {0}")]
+    Decode(#[from] bincode::error::DecodeError),
 }
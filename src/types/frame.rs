@@ -1,12 +1,15 @@
 use dbc_editor::types::database::{DatabaseDBC, MessageKey, NodeKey, SignalKey};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
 pub struct Frame {
     /// Absolute timestamp in `%Y-%m-%d %H:%M:%S%.3f` when available,
     /// otherwise derived by the parser.
     pub absolute_time: String,
 
     /// Relative timestamp in seconds since trace start (parsed from the textual token).
+    /// Inside a trigger block it is relative to the block start, see
+    /// `Log::block_adjusted_timestamp`.
     pub timestamp: f64,
 
     /// Logger channel index (typically 1-based).
@@ -34,6 +37,19 @@ pub struct Frame {
 
     /// Payload bytes as hex pairs separated by spaces.
     pub data: String,
+
+    /// CAN-FD bit rate switch flag (`BrsOn` token).
+    pub brs_enabled: bool,
+    /// CAN-FD error state indicator flag (`EsiError` token).
+    pub esi_error: bool,
+
+    /// Frame recorded inside a `DISTURBANCE begin` / `DISTURBANCE end` block, see
+    /// `ParseOptions::tag_disturbed_frames`.
+    pub is_disturbed: bool,
+
+    /// Index in `Log::trigger_blocks` of the block the frame was recorded in,
+    /// `None` outside trigger blocks.
+    pub trigger_block: Option<usize>,
 }
 
 impl Frame {
@@ -58,6 +74,115 @@ impl Frame {
         format!("{}", self.byte_length)
     }
 
+    /// Return the id as a 3 digit uppercase hex string, e.g. `"123"`.
+    pub fn fmt_id_standard(&self) -> String {
+        format!("{:03X}", self.id)
+    }
+
+    /// Return the id as an 8 digit uppercase hex string with the `x` marker,
+    /// e.g. `"17334410x"`, as written by Vector loggers for extended ids.
+    pub fn fmt_id_extended(&self) -> String {
+        format!("{:08X}x", self.id)
+    }
+
+    /// Return the id as a decimal string, e.g. `"291"`.
+    pub fn fmt_id_decimal(&self) -> String {
+        format!("{}", self.id)
+    }
+
+    /// Decode the payload bytes from the space separated hex pairs in `data`.
+    /// Tokens that are not valid hex are skipped.
+    pub fn payload_bytes(&self) -> Vec<u8> {
+        self.data
+            .split_ascii_whitespace()
+            .filter_map(|tok| u8::from_str_radix(tok, 16).ok())
+            .collect()
+    }
+
+    /// Replace the payload, updating `data` (uppercase hex pairs) and `byte_length`.
+    pub fn set_payload(&mut self, bytes: &[u8]) {
+        self.data = bytes
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<Vec<String>>()
+            .join(" ");
+        self.byte_length = bytes.len() as u16;
+    }
+
+    /// Return true if the identifier needs the 29-bit extended format,
+    /// either because of its value or because the log marked it with `x`.
+    pub fn is_extended_id(&self) -> bool {
+        self.id > 0x7FF || self.id_hex.ends_with(['x', 'X'])
+    }
+
+    /// Approximate number of bits of the Frame on the wire (bit stuffing ignored).
+    /// Standard frames carry 47 bits of overhead, extended frames 67 bits. Remote
+    /// frames have no data field whatever their DLC.
+    pub fn estimated_bit_count(&self) -> u32 {
+        let overhead: u32 = if self.is_extended_id() { 67 } else { 47 };
+        if self.ftype == FrameType::Remote {
+            return overhead;
+        }
+        overhead + 8 * u32::from(self.byte_length)
+    }
+
+    /// Format the Frame as a single Vector ASC line, without line terminator:
+    /// - CAN: `"{timestamp:.6} {channel} {id} {direction} d {byte_length} {data}"`;
+    /// - CAN-FD (more than 8 bytes): `"{timestamp:.6} CANFD {channel} {id} {direction}
+    ///   BrsOn|BrsOff EsiError|EsiValid d {byte_length} {data}"`;
+    /// - error frames: `"{timestamp:.6} {channel} ErrorFrame"`;
+    /// - remote frames: `"{timestamp:.6} {channel} {id} {direction} r {byte_length}"`;
+    /// - Ethernet: `"{timestamp:.6} ETH {channel} {direction} {data}"`.
+    ///
+    /// `id_hex` is written as is, frames built without it use the hex id with the
+    /// `x` marker for extended ids.
+    ///
+    /// Parsing the line gives back the same frame for CAN frames, CAN-FD frames of
+    /// more than 8 bytes, remote frames and error frames. CAN-FD frames of up to 8
+    /// bytes come back as CAN frames without the `BrsOn`/`EsiError` flags. Ethernet
+    /// frames do not round-trip: the parser drops the line.
+    pub fn to_asc_line(&self) -> String {
+        let id: String = if self.id_hex.is_empty() {
+            if self.id > 0x7FF {
+                format!("{:X}x", self.id)
+            } else {
+                format!("{:X}", self.id)
+            }
+        } else {
+            self.id_hex.clone()
+        };
+        match self.ftype {
+            FrameType::ErrorFrame => format!("{:.6} {} ErrorFrame", self.timestamp, self.channel),
+            FrameType::Remote => format!(
+                "{:.6} {} {} {} r {}",
+                self.timestamp, self.channel, id, self.direction, self.byte_length
+            ),
+            FrameType::Eth => format!(
+                "{:.6} ETH {} {} {}",
+                self.timestamp, self.channel, self.direction, self.data
+            ),
+            FrameType::Can if self.byte_length > 8 => format!(
+                "{:.6} CANFD {} {} {} {} {} d {} {}",
+                self.timestamp,
+                self.channel,
+                id,
+                self.direction,
+                if self.brs_enabled { "BrsOn" } else { "BrsOff" },
+                if self.esi_error {
+                    "EsiError"
+                } else {
+                    "EsiValid"
+                },
+                self.byte_length,
+                self.data
+            ),
+            FrameType::Can => format!(
+                "{:.6} {} {} {} d {} {}",
+                self.timestamp, self.channel, id, self.direction, self.byte_length, self.data
+            ),
+        }
+    }
+
     /// Return the CAN Protocol of the Frame
     pub fn protocol_to_string(&self) -> String {
         if self.byte_length <= 8 {
@@ -93,9 +218,166 @@ impl Frame {
             "".to_string()
         }
     }
+
+    /// Return the signals carried by both frames, in the order of `self.sig_keys`.
+    ///
+    /// Signals are compared by `SignalKey`, so two frames of a multiplexed message
+    /// share only the signals decoded for both multiplexor values. Keys not found
+    /// in `db` are ignored.
+    pub fn overlapping_signals(&self, other: &Frame, db: &DatabaseDBC) -> Vec<SignalKey> {
+        let mut common: Vec<SignalKey> = Vec::new();
+        for sig_key in &self.sig_keys {
+            if other.sig_keys.contains(sig_key)
+                && !common.contains(sig_key)
+                && db.get_sig_by_key(*sig_key).is_some()
+            {
+                common.push(*sig_key);
+            }
+        }
+        common
+    }
+}
+
+/// Builder for `Frame`, mostly meant for test fixtures.
+///
+/// Every setter is optional since `Frame::default()` is a valid frame. When
+/// `byte_length` is not set it is derived from the tokens of `data`.
+///
+/// ```ignore
+/// let frame = FrameBuilder::new()
+///     .timestamp(0.5)
+///     .channel(1)
+///     .id(0x123)
+///     .direction(Direction::Rx)
+///     .data("AA BB")
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FrameBuilder {
+    frame: Frame,
+    byte_length: Option<u16>,
+}
+
+impl FrameBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn absolute_time(mut self, absolute_time: &str) -> Self {
+        self.frame.absolute_time = absolute_time.to_string();
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: f64) -> Self {
+        self.frame.timestamp = timestamp;
+        self
+    }
+
+    pub fn channel(mut self, channel: u8) -> Self {
+        self.frame.channel = channel;
+        self
+    }
+
+    pub fn ftype(mut self, ftype: FrameType) -> Self {
+        self.frame.ftype = ftype;
+        self
+    }
+
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.frame.direction = direction;
+        self
+    }
+
+    pub fn msg_key(mut self, msg_key: MessageKey) -> Self {
+        self.frame.msg_key = msg_key;
+        self
+    }
+
+    /// Set the identifier and replace `id_hex` with its ASC token, hex with the `x`
+    /// marker for extended ids. Call `id_hex` afterwards to keep a different token.
+    pub fn id(mut self, id: u32) -> Self {
+        self.frame.id = id;
+        self.frame.id_hex = if id > 0x7FF {
+            format!("{:X}x", id)
+        } else {
+            format!("{:X}", id)
+        };
+        self
+    }
+
+    pub fn id_hex(mut self, id_hex: &str) -> Self {
+        self.frame.id_hex = id_hex.to_string();
+        self
+    }
+
+    pub fn byte_length(mut self, byte_length: u16) -> Self {
+        self.byte_length = Some(byte_length);
+        self
+    }
+
+    pub fn tx_node_key(mut self, tx_node_key: NodeKey) -> Self {
+        self.frame.tx_node_key = tx_node_key;
+        self
+    }
+
+    pub fn sig_keys(mut self, sig_keys: Vec<SignalKey>) -> Self {
+        self.frame.sig_keys = sig_keys;
+        self
+    }
+
+    pub fn data(mut self, data: &str) -> Self {
+        self.frame.data = data.to_string();
+        self
+    }
+
+    pub fn brs_enabled(mut self, brs_enabled: bool) -> Self {
+        self.frame.brs_enabled = brs_enabled;
+        self
+    }
+
+    pub fn esi_error(mut self, esi_error: bool) -> Self {
+        self.frame.esi_error = esi_error;
+        self
+    }
+
+    pub fn is_disturbed(mut self, is_disturbed: bool) -> Self {
+        self.frame.is_disturbed = is_disturbed;
+        self
+    }
+
+    /// Build the `Frame`.
+    ///
+    /// Remote frames keep the `byte_length` they were given, their DLC, since they
+    /// carry no data.
+    ///
+    /// # Panics
+    /// If `byte_length` was set and differs from the number of tokens in `data`,
+    /// remote frames excepted.
+    pub fn build(self) -> Frame {
+        let mut frame: Frame = self.frame;
+        let tokens: usize = frame.data.split_ascii_whitespace().count();
+        match self.byte_length {
+            Some(byte_length) if frame.ftype == FrameType::Remote => {
+                frame.byte_length = byte_length;
+            }
+            Some(byte_length) => {
+                assert!(
+                    byte_length as usize == tokens,
+                    "FrameBuilder: byte_length is {} but data \"{}\" has {} bytes",
+                    byte_length,
+                    frame.data,
+                    tokens
+                );
+                frame.byte_length = byte_length;
+            }
+            None => frame.byte_length = tokens as u16,
+        }
+        frame
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     #[default]
     Rx,
@@ -112,11 +394,14 @@ impl std::fmt::Display for Direction {
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
 pub enum FrameType {
     #[default]
     Can,
     Eth,
     ErrorFrame,
+    /// CAN remote transmission request: a DLC but no payload.
+    Remote,
 }
 impl std::fmt::Display for FrameType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -124,7 +409,70 @@ impl std::fmt::Display for FrameType {
             FrameType::Can => "Can",
             FrameType::Eth => "Eth",
             FrameType::ErrorFrame => "ErrorFrame",
+            FrameType::Remote => "Remote",
         };
         f.write_str(label)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::line::LineParser;
+    use crate::types::log::Log;
+
+    /// The frames parsed from `lines`, in file order.
+    fn parse_lines(lines: &[String]) -> Vec<Frame> {
+        let mut log: Log = Log::default();
+        log.add_can_channel(1, None);
+        let mut parser: LineParser = LineParser::new();
+        for line in lines {
+            parser.parse(line, &mut log);
+        }
+        log.frame_by_file_order
+            .iter()
+            .map(|key| log.frames[*key].clone())
+            .collect()
+    }
+
+    #[test]
+    fn asc_line_round_trip() {
+        let lines: Vec<String> = [
+            "0.016728 1 123 Rx d 8 3E 42 03 00 39 00 03 01",
+            "1.500000 1 1FFFFFFFx Tx d 2 AA BB",
+            "2.000000 CANFD 1 18FF0010x Rx BrsOn EsiError d 12 00 11 22 33 44 55 66 77 88 99 AA BB",
+            "3.250000 1 7FF Rx r 4",
+            "3.500000 1 ErrorFrame",
+        ]
+        .map(String::from)
+        .to_vec();
+        let frames: Vec<Frame> = parse_lines(&lines);
+        assert_eq!(frames.len(), lines.len());
+
+        let emitted: Vec<String> = frames.iter().map(Frame::to_asc_line).collect();
+        assert_eq!(parse_lines(&emitted), frames);
+    }
+
+    #[test]
+    fn builder_keeps_the_dlc_of_remote_frames() {
+        let frame: Frame = FrameBuilder::new()
+            .ftype(FrameType::Remote)
+            .id(0x1A3)
+            .byte_length(4)
+            .build();
+        assert_eq!(frame.byte_length, 4);
+        assert!(frame.data.is_empty());
+    }
+
+    #[test]
+    fn builder_id_replaces_id_hex() {
+        let frame: Frame = FrameBuilder::new().id_hex("123").id(0x456).build();
+        assert_eq!(frame.id_hex, "456");
+
+        let frame: Frame = FrameBuilder::new().id(0x18FF0010).build();
+        assert_eq!(frame.id_hex, "18FF0010x");
+
+        let frame: Frame = FrameBuilder::new().id(0x10).id_hex("10x").build();
+        assert_eq!(frame.id_hex, "10x");
+    }
+}
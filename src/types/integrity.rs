@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+
+use crate::core::sort;
+use crate::types::frame::FrameType;
+use crate::types::keys::FrameKey;
+use crate::types::log::Log;
+
+/// Result of `Log::verify_integrity`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntegrityReport {
+    /// `true` when no violation was found.
+    pub ok: bool,
+    /// Human readable description of every violation.
+    pub errors: Vec<String>,
+}
+
+impl Log {
+    /// Checks the internal invariants of the `Log`, useful after mutations or
+    /// deserialization:
+    /// - every key of every sorted view exists in `frames`;
+    /// - `frame_by_file_order` holds each frame exactly once;
+    /// - `id_chn_by_*` views hold a single key per `(id, channel)` pair;
+    /// - every sorted view is ordered by its sort key.
+    ///
+    /// Available in debug builds, or in release with the `integrity` feature.
+    pub fn verify_integrity(&self) -> IntegrityReport {
+        let mut errors: Vec<String> = Vec::new();
+
+        for (name, keys) in self.named_views() {
+            let missing: usize = keys
+                .iter()
+                .filter(|key| !self.frames.contains_key(**key))
+                .count();
+            if missing > 0 {
+                errors.push(format!("{}: {} keys not found in frames", name, missing));
+            }
+        }
+
+        if self.frame_by_file_order.len() != self.frames.len() {
+            errors.push(format!(
+                "frame_by_file_order: {} keys for {} frames",
+                self.frame_by_file_order.len(),
+                self.frames.len()
+            ));
+        }
+        let unique: HashSet<FrameKey> = self.frame_by_file_order.iter().copied().collect();
+        if unique.len() != self.frame_by_file_order.len() {
+            errors.push(format!(
+                "frame_by_file_order: {} duplicated keys",
+                self.frame_by_file_order.len() - unique.len()
+            ));
+        }
+
+        for (name, keys) in self.named_views() {
+            if !name.starts_with("id_chn_") {
+                continue;
+            }
+            let mut pairs: HashSet<(u32, u8)> = HashSet::new();
+            let duplicates: usize = keys
+                .iter()
+                .filter_map(|key| self.frames.get(*key))
+                .filter(|frame| frame.ftype == FrameType::Can)
+                .filter(|frame| !pairs.insert((frame.id, frame.channel)))
+                .count();
+            if duplicates > 0 {
+                errors.push(format!(
+                    "{}: {} duplicated (id, channel) pairs",
+                    name, duplicates
+                ));
+            }
+        }
+
+        errors.extend(sort::unsorted_views(self));
+
+        IntegrityReport {
+            ok: errors.is_empty(),
+            errors,
+        }
+    }
+
+    fn named_views(&self) -> Vec<(&'static str, &[FrameKey])> {
+        vec![
+            ("frame_by_file_order", &self.frame_by_file_order),
+            ("frame_by_timestamp", &self.frame_by_timestamp),
+            ("frame_by_block_timestamp", &self.frame_by_block_timestamp),
+            ("frame_by_channel", &self.frame_by_channel),
+            ("frame_by_direction", &self.frame_by_direction),
+            ("frame_by_can_msg_name", &self.frame_by_can_msg_name),
+            ("frame_by_can_signal_name", &self.frame_by_can_signal_name),
+            ("frame_by_can_msg_id", &self.frame_by_can_msg_id),
+            ("frame_by_can_dlc", &self.frame_by_can_dlc),
+            ("frame_by_can_protocol", &self.frame_by_can_protocol),
+            ("frame_by_protocol", &self.frame_by_protocol),
+            ("frame_by_can_priority", &self.frame_by_can_priority),
+            ("frame_by_can_cyclic_only", &self.frame_by_can_cyclic_only),
+            (
+                "frame_by_can_event_driven_only",
+                &self.frame_by_can_event_driven_only,
+            ),
+            ("frame_by_can_sender_node", &self.frame_by_can_sender_node),
+            ("frame_by_can_data", &self.frame_by_can_data),
+            ("frame_by_can_comment", &self.frame_by_can_comment),
+            ("id_chn_by_timestamp", &self.id_chn_by_timestamp),
+            ("id_chn_by_channel", &self.id_chn_by_channel),
+            ("id_chn_by_direction", &self.id_chn_by_direction),
+            ("id_chn_by_can_msg_name", &self.id_chn_by_can_msg_name),
+            ("id_chn_by_can_msg_id", &self.id_chn_by_can_msg_id),
+            ("id_chn_by_can_dlc", &self.id_chn_by_can_dlc),
+            ("id_chn_by_can_protocol", &self.id_chn_by_can_protocol),
+            ("id_chn_by_can_sender_node", &self.id_chn_by_can_sender_node),
+            ("id_chn_by_can_data", &self.id_chn_by_can_data),
+            ("id_chn_by_can_comment", &self.id_chn_by_can_comment),
+        ]
+    }
+}
@@ -1,12 +1,27 @@
-use dbc_editor::types::database::DatabaseDBC;
+use chrono::NaiveDateTime;
+use dbc_editor::types::database::{DatabaseDBC, MessageKey, NodeKey, SignalKey};
 use slotmap::SlotMap;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
 
+#[cfg(feature = "bincode")]
+use crate::checkpoint::CheckpointCursor;
+use crate::core::{line, sort};
 use crate::types::absolute_time::AbsoluteTime;
-use crate::types::frame::Frame;
+use crate::types::errors::{ParseError, SignalScaleError};
+use crate::types::frame::{Frame, FrameType};
 use crate::types::keys::FrameKey;
+use crate::types::plot_data::PlotData;
+use crate::types::signal_event::SignalChangeEvent;
+use crate::types::statistics::{ErrorCountEntry, FdBusStatEntry, MessageStats, SignalSummaryRow};
+use crate::types::topology::NodeTopology;
+use crate::types::trigger::{self, Trigger, TriggerBlock};
+#[cfg(feature = "fft")]
+use crate::utils::fft::{self, WindowFunction};
+use crate::utils::{entropy, lttb, resample};
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
 pub struct Log {
     /// Vector containing all channelInfo starting from 0.
     pub channel_map: HashMap<u8, ChannelInfo>,
@@ -14,20 +29,63 @@ pub struct Log {
     /// Absolute start time extracted from the `date` header, if present.
     pub absolute_time: AbsoluteTime,
 
+    /// Version of the `version` header line, e.g. `"8.0.0"`, if present.
+    pub asc_version: Option<String>,
+
+    /// `//` comment lines of the file as `(line_number, text)`, line numbers are 1-based.
+    pub comments: Vec<(usize, String)>,
+
+    /// `CANFD_BUSSTATISTICS` lines of the file.
+    pub fd_bus_statistics: Vec<FdBusStatEntry>,
+
+    /// `ERRORCOUNT` lines of the file.
+    pub error_counts: Vec<ErrorCountEntry>,
+
+    /// `TRIGGER` event markers of the file, sorted by timestamp.
+    pub triggers: Vec<Trigger>,
+
+    /// `Begin Triggerblock` sections of the file, in file order.
+    pub trigger_blocks: Vec<TriggerBlock>,
+
+    /// Keywords of unsupported lines and how often they were skipped, in order of
+    /// first occurrence. Only filled with `ParseOptions::collect_skipped_tokens`.
+    pub skipped_tokens: Vec<(String, usize)>,
+
+    /// Recoverable problems met while parsing, see `ParseOptions::allow_truncated`.
+    pub parse_warnings: Vec<String>,
+
     /// All parsed frames in file order.
     pub frames: SlotMap<FrameKey, Frame>,
 
     // --- All Frames Order by generic parameters  ---
     pub frame_by_file_order: Vec<FrameKey>,
+    /// Frames ordered by `Log::block_adjusted_timestamp`.
     pub frame_by_timestamp: Vec<FrameKey>,
+    /// Frames ordered by trigger block, then by timestamp within the block. Frames
+    /// outside trigger blocks come first. Same as `frame_by_timestamp` for files
+    /// without trigger blocks.
+    pub frame_by_block_timestamp: Vec<FrameKey>,
     pub frame_by_channel: Vec<FrameKey>,
     pub frame_by_direction: Vec<FrameKey>,
 
     // ---  All Frames Order by CAN parameters   ---
     pub frame_by_can_msg_name: Vec<FrameKey>,
+    /// CAN frames ordered by the lexicographically smallest signal name they carry.
+    pub frame_by_can_signal_name: Vec<FrameKey>,
     pub frame_by_can_msg_id: Vec<FrameKey>,
+    /// CAN frames ordered by data length, then by id within the same length.
     pub frame_by_can_dlc: Vec<FrameKey>,
     pub frame_by_can_protocol: Vec<FrameKey>,
+    /// CAN frames grouped by protocol (CAN first, then CAN-FD), then by channel and id.
+    pub frame_by_protocol: Vec<FrameKey>,
+    /// CAN frames ordered by J1939 priority (id bits 26..29), then channel and id.
+    /// Standard id frames have no priority and come last.
+    pub frame_by_can_priority: Vec<FrameKey>,
+    /// CAN frames of the (id, channel) pairs transmitted cyclically, ordered by timestamp.
+    /// See `Log::classify_cyclic_frames` for the criteria.
+    pub frame_by_can_cyclic_only: Vec<FrameKey>,
+    /// CAN frames of every other (id, channel) pair, ordered by timestamp.
+    pub frame_by_can_event_driven_only: Vec<FrameKey>,
     pub frame_by_can_sender_node: Vec<FrameKey>,
     pub frame_by_can_data: Vec<FrameKey>,
     pub frame_by_can_comment: Vec<FrameKey>,
@@ -40,11 +98,30 @@ pub struct Log {
     // --- ID-Channel Order by CAN parameters  ---
     pub id_chn_by_can_msg_name: Vec<FrameKey>,
     pub id_chn_by_can_msg_id: Vec<FrameKey>,
+    /// Same ordering as `frame_by_can_dlc`, one frame per (id, channel) pair.
     pub id_chn_by_can_dlc: Vec<FrameKey>,
     pub id_chn_by_can_protocol: Vec<FrameKey>,
     pub id_chn_by_can_sender_node: Vec<FrameKey>,
     pub id_chn_by_can_data: Vec<FrameKey>,
     pub id_chn_by_can_comment: Vec<FrameKey>,
+
+    /// What `save_checkpoint_incremental` already wrote, `None` forces a full save.
+    #[cfg(feature = "bincode")]
+    #[serde(skip)]
+    pub(crate) checkpoint_cursor: Option<CheckpointCursor>,
+
+    /// Lazily built result of `group_frames_by_msg_name`, see `get_frames_by_msg_name`.
+    #[cfg_attr(feature = "bincode", serde(skip))]
+    msg_name_cache: OnceLock<HashMap<String, Vec<FrameKey>>>,
+
+    /// Lazily built `(id, channel)` -> `FrameKey` index of `id_chn_by_timestamp`,
+    /// see `most_recent_frame_by_id`.
+    #[cfg_attr(feature = "bincode", serde(skip))]
+    id_chn_index: OnceLock<HashMap<(u32, u8), FrameKey>>,
+
+    /// Lazily built result of `build_frequency_map`, see `get_or_build_frequency_cache`.
+    #[cfg_attr(feature = "bincode", serde(skip))]
+    frequency_cache: OnceLock<HashMap<(u32, u8), usize>>,
 }
 
 impl Log {
@@ -55,18 +132,33 @@ impl Log {
 
     pub fn clear_frames(&mut self) {
         self.frames.clear();
+        self.asc_version = None;
+        self.comments.clear();
+        self.fd_bus_statistics.clear();
+        self.error_counts.clear();
+        self.triggers.clear();
+        self.trigger_blocks.clear();
+        self.skipped_tokens.clear();
+        self.parse_warnings.clear();
+        self.reset_checkpoint_cursor();
 
         // --- All Frames Order by generic parameters  ---
         self.frame_by_file_order.clear();
         self.frame_by_timestamp.clear();
+        self.frame_by_block_timestamp.clear();
         self.frame_by_channel.clear();
         self.frame_by_direction.clear();
 
         // ---  All Frames Order by CAN parameters   ---
         self.frame_by_can_msg_name.clear();
+        self.frame_by_can_signal_name.clear();
         self.frame_by_can_msg_id.clear();
         self.frame_by_can_dlc.clear();
         self.frame_by_can_protocol.clear();
+        self.frame_by_protocol.clear();
+        self.frame_by_can_priority.clear();
+        self.frame_by_can_cyclic_only.clear();
+        self.frame_by_can_event_driven_only.clear();
         self.frame_by_can_sender_node.clear();
         self.frame_by_can_data.clear();
         self.frame_by_can_comment.clear();
@@ -84,6 +176,117 @@ impl Log {
         self.id_chn_by_can_sender_node.clear();
         self.id_chn_by_can_data.clear();
         self.id_chn_by_can_comment.clear();
+
+        self.invalidate_caches();
+    }
+
+    /// Drop every lazily built cache, to be called whenever frames or databases change.
+    pub(crate) fn invalidate_caches(&mut self) {
+        self.msg_name_cache.take();
+        self.id_chn_index.take();
+        self.invalidate_frequency_cache();
+    }
+
+    /// Make the next `save_checkpoint_incremental` a full save, to be called whenever
+    /// frames or signal samples already saved are changed in place or removed.
+    pub(crate) fn reset_checkpoint_cursor(&mut self) {
+        #[cfg(feature = "bincode")]
+        {
+            self.checkpoint_cursor = None;
+        }
+    }
+
+    /// Drop the cache of `get_or_build_frequency_cache`.
+    pub fn invalidate_frequency_cache(&mut self) {
+        self.frequency_cache.take();
+    }
+
+    /// Remove duplicated keys from every view and return how many were removed.
+    ///
+    /// Duplicates of a key share the same sort key, so in sorted views they are
+    /// adjacent and a `dedup` pass is enough. `frame_by_file_order` keeps the first
+    /// occurrence through a `HashSet` pass. O(N) per view.
+    pub fn dedup_sorted_views(&mut self) -> usize {
+        let mut removed: usize = 0;
+
+        let mut seen: HashSet<FrameKey> = HashSet::with_capacity(self.frame_by_file_order.len());
+        let before: usize = self.frame_by_file_order.len();
+        self.frame_by_file_order.retain(|key| seen.insert(*key));
+        removed += before - self.frame_by_file_order.len();
+
+        for view in self.sorted_views_mut() {
+            let before: usize = view.len();
+            view.dedup();
+            removed += before - view.len();
+        }
+
+        if removed > 0 {
+            self.invalidate_caches();
+        }
+        removed
+    }
+
+    /// Remove the frames of `keys` and return how many were actually removed.
+    ///
+    /// Keys not in `frames` are skipped. `frame_by_file_order` is filtered in a
+    /// single pass against a `HashSet` of `keys` and the sorted views are rebuilt
+    /// once at the end. Decoded signal values stored in the databases are left
+    /// untouched.
+    pub fn batch_remove_frames(&mut self, keys: &[FrameKey]) -> usize {
+        let to_remove: HashSet<FrameKey> = keys
+            .iter()
+            .copied()
+            .filter(|key| self.frames.contains_key(*key))
+            .collect();
+        if to_remove.is_empty() {
+            return 0;
+        }
+
+        // the frames already saved change, the next incremental checkpoint is a full one
+        self.reset_checkpoint_cursor();
+
+        for key in &to_remove {
+            self.frames.remove(*key);
+        }
+        self.frame_by_file_order
+            .retain(|key| !to_remove.contains(key));
+        for block in &mut self.trigger_blocks {
+            block.frame_keys.retain(|key| !to_remove.contains(key));
+        }
+        sort::rebuild_views(self);
+        to_remove.len()
+    }
+
+    /// Every view derived from `frame_by_file_order` by sorting.
+    fn sorted_views_mut(&mut self) -> Vec<&mut Vec<FrameKey>> {
+        vec![
+            &mut self.frame_by_timestamp,
+            &mut self.frame_by_block_timestamp,
+            &mut self.frame_by_channel,
+            &mut self.frame_by_direction,
+            &mut self.frame_by_can_msg_name,
+            &mut self.frame_by_can_signal_name,
+            &mut self.frame_by_can_msg_id,
+            &mut self.frame_by_can_dlc,
+            &mut self.frame_by_can_protocol,
+            &mut self.frame_by_protocol,
+            &mut self.frame_by_can_priority,
+            &mut self.frame_by_can_cyclic_only,
+            &mut self.frame_by_can_event_driven_only,
+            &mut self.frame_by_can_sender_node,
+            &mut self.frame_by_can_data,
+            &mut self.frame_by_can_comment,
+            &mut self.id_chn_by_timestamp,
+            &mut self.id_chn_by_channel,
+            &mut self.id_chn_by_direction,
+            &mut self.id_chn_by_can_msg_name,
+            &mut self.id_chn_by_can_msg_id,
+            &mut self.id_chn_by_can_dlc,
+            &mut self.id_chn_by_can_protocol,
+            &mut self.id_chn_by_can_sender_node,
+            &mut self.id_chn_by_can_data,
+            &mut self.id_chn_by_can_comment,
+        ]
     }
 
     /// Check if there are any frames present
@@ -91,6 +294,196 @@ impl Log {
         self.frames.is_empty()
     }
 
+    /// Describe what the parser skipped, one human-readable line per kind, e.g.
+    /// `"12 FlexRay lines"` or `"3 unknown tokens: [Statistic:, GPS]"`.
+    ///
+    /// Built from `skipped_tokens`, so it is empty unless the file was parsed with
+    /// `ParseOptions::collect_skipped_tokens`.
+    pub fn has_unsupported_features(&self) -> Vec<String> {
+        let mut features: Vec<String> = Vec::new();
+        let mut unknown: Vec<&str> = Vec::new();
+        let mut unknown_count: usize = 0;
+        for (token, count) in &self.skipped_tokens {
+            let feature: Option<&str> = match token.to_ascii_uppercase().as_str() {
+                "FR" | "FLEXRAY" => Some("FlexRay"),
+                "LIN" => Some("LIN"),
+                "J1939" => Some("J1939"),
+                "MOST" => Some("MOST"),
+                "GPS" => Some("GPS"),
+                _ => None,
+            };
+            match feature {
+                Some(feature) => features.push(format!("{} {} lines", count, feature)),
+                None => {
+                    unknown.push(token);
+                    unknown_count += count;
+                }
+            }
+        }
+        if !unknown.is_empty() {
+            features.push(format!(
+                "{} unknown tokens: [{}]",
+                unknown_count,
+                unknown.join(", ")
+            ));
+        }
+        features
+    }
+
+    /// Return the triggers with `start <= timestamp <= end`, found by binary search.
+    /// The range is in block-adjusted time, see `block_adjusted_timestamp`.
+    pub fn triggers_in_range(&self, start: f64, end: f64) -> &[Trigger] {
+        let first: usize = self.triggers.partition_point(|t| t.timestamp < start);
+        let last: usize = self.triggers.partition_point(|t| t.timestamp <= end);
+        &self.triggers[first..last.max(first)]
+    }
+
+    /// Return the slice of `frame_by_block_timestamp` whose wall-clock time lies in
+    /// `start..=end`. The wall-clock time of a frame is `absolute_time` plus the
+    /// `start_timestamp` of its trigger block plus the frame timestamp.
+    ///
+    /// `start` and `end` use the `"YYYY-MM-DD HH:MM:SS.mmm"` format, the fraction
+    /// being optional. Trigger blocks are expected in chronological order without
+    /// overlap, as loggers write them. The slice is empty when the file has no
+    /// `date` header.
+    pub fn frame_by_absolute_timestamp_range(
+        &self,
+        start: &str,
+        end: &str,
+    ) -> Result<&[FrameKey], ParseError> {
+        let parse = |value: &str| {
+            NaiveDateTime::parse_from_str(value.trim(), "%Y-%m-%d %H:%M:%S%.f").map_err(|_| {
+                ParseError::InvalidTimestampFormat {
+                    value: value.to_string(),
+                }
+            })
+        };
+        let start: NaiveDateTime = parse(start)?;
+        let end: NaiveDateTime = parse(end)?;
+        let Some(base) = self.absolute_time.value else {
+            return Ok(&[]);
+        };
+
+        // the view is sorted by block then timestamp, which is monotone in wall-clock time
+        let start_s: f64 = (start - base).num_milliseconds() as f64 / 1000.0;
+        let end_s: f64 = (end - base).num_milliseconds() as f64 / 1000.0;
+        let view: &[FrameKey] = &self.frame_by_block_timestamp;
+        let offset = |key: &FrameKey| {
+            self.frames
+                .get(*key)
+                .map_or(f64::MAX, |frame| self.block_adjusted_timestamp(frame))
+        };
+        let first: usize = view.partition_point(|key| offset(key) < start_s - SIGNAL_EPSILON);
+        let last: usize = view.partition_point(|key| offset(key) <= end_s + SIGNAL_EPSILON);
+        Ok(&view[first..last.max(first)])
+    }
+
+    /// Seconds from `absolute_time` to `frame`: the start of its trigger block plus
+    /// its timestamp.
+    ///
+    /// This is the time used by `frame_by_timestamp`, `id_chn_by_timestamp`, the
+    /// signal samples, the triggers and the timing analyses, so frames of different
+    /// trigger blocks never interleave. Without trigger blocks it is `Frame::timestamp`.
+    pub fn block_adjusted_timestamp(&self, frame: &Frame) -> f64 {
+        trigger::block_adjusted_timestamp(&self.trigger_blocks, frame)
+    }
+
+    /// Return, in timestamp order, the frames whose wall-clock time of day lies
+    /// between `from_hour:from_minute` and `to_hour:to_minute`, both minutes included.
+    ///
+    /// Compares the `HH:MM` part of `Frame::absolute_time`. A range ending before it
+    /// starts wraps around midnight. Empty when the file has no `date` header.
+    pub fn frames_by_time_of_day(
+        &self,
+        from_hour: u8,
+        from_minute: u8,
+        to_hour: u8,
+        to_minute: u8,
+    ) -> Vec<FrameKey> {
+        if self.absolute_time.value.is_none() {
+            return Vec::new();
+        }
+        let from: String = format!("{:02}:{:02}", from_hour, from_minute);
+        let to: String = format!("{:02}:{:02}", to_hour, to_minute);
+        let in_range = |hhmm: &str| {
+            if from <= to {
+                from.as_str() <= hhmm && hhmm <= to.as_str()
+            } else {
+                from.as_str() <= hhmm || hhmm <= to.as_str()
+            }
+        };
+        self.frame_by_timestamp
+            .iter()
+            .copied()
+            .filter(|key| {
+                self.frames
+                    .get(*key)
+                    .and_then(|frame| frame.absolute_time.split_once(' '))
+                    .and_then(|(_, time)| time.get(..5))
+                    .is_some_and(in_range)
+            })
+            .collect()
+    }
+
+    /// Split the CAN frames into `frame_by_can_cyclic_only` and
+    /// `frame_by_can_event_driven_only` with custom thresholds.
+    ///
+    /// An (id, channel) pair is cyclic if it has more than `min_repeat_count` frames
+    /// and the coefficient of variation of its inter-frame gaps is below `max_gap_cv`.
+    /// The views are built with `CYCLIC_MIN_REPEAT_COUNT` and `CYCLIC_MAX_GAP_CV`
+    /// whenever the sorted views are rebuilt.
+    pub fn classify_cyclic_frames(&mut self, min_repeat_count: usize, max_gap_cv: f64) {
+        sort::rebuild_cyclic_views(self, min_repeat_count, max_gap_cv);
+    }
+
+    /// Mutable access to the channel configuration.
+    pub fn channel_map_mut(&mut self) -> &mut HashMap<u8, ChannelInfo> {
+        &mut self.channel_map
+    }
+
+    /// Configure `number` as a CAN channel, replacing any previous configuration.
+    pub fn add_can_channel(&mut self, number: u8, database: Option<DatabaseDBC>) -> &mut Self {
+        self.channel_map.insert(
+            number,
+            ChannelInfo {
+                number,
+                tipo: ChannelType::Can,
+                database,
+                ..ChannelInfo::default()
+            },
+        );
+        self
+    }
+
+    /// Configure `number` as an Ethernet channel, replacing any previous configuration.
+    pub fn add_ethernet_channel(&mut self, number: u8) -> &mut Self {
+        self.channel_map.insert(
+            number,
+            ChannelInfo {
+                number,
+                tipo: ChannelType::Ethernet,
+                ..ChannelInfo::default()
+            },
+        );
+        self
+    }
+
+    /// Return the number of the channel whose `namespace` is `ns`.
+    pub fn get_channel_by_namespace(&self, ns: &str) -> Option<u8> {
+        self.channel_map
+            .values()
+            .find(|info| info.namespace.as_deref() == Some(ns))
+            .map(|info| info.number)
+    }
+
+    /// `ChannelInfo::display_name` of `ch`, `"Channel {ch}"` for an unknown channel.
+    pub fn channel_display_name(&self, ch: u8) -> String {
+        match self.channel_map.get(&ch) {
+            Some(info) => info.display_name(),
+            None => format!("Channel {}", ch),
+        }
+    }
+
     pub fn get_database_by_channel(&self, ch: u8) -> Option<&DatabaseDBC> {
         if let Some(ch_info) = self.channel_map.get(&ch) {
             ch_info.database.as_ref()
@@ -108,18 +501,2009 @@ impl Log {
     pub fn get_frame_by_key(&self, frame_key: &FrameKey) -> Option<&Frame> {
         self.frames.get(*frame_key)
     }
+
+    /// Return the DBC message name of the frame `key`.
+    pub fn message_name_for_key(&self, key: FrameKey) -> Option<String> {
+        let frame: &Frame = self.frames.get(key)?;
+        let db: &DatabaseDBC = self.get_database_by_channel(frame.channel)?;
+        Some(db.get_message_by_key(frame.msg_key)?.name.clone())
+    }
+
+    /// Return the name of the DBC sender node of the frame `key`.
+    pub fn sender_node_for_key(&self, key: FrameKey) -> Option<String> {
+        let frame: &Frame = self.frames.get(key)?;
+        let db: &DatabaseDBC = self.get_database_by_channel(frame.channel)?;
+        Some(db.get_node_by_key(frame.tx_node_key)?.name.clone())
+    }
+
+    /// Return the DBC message comment of the frame `key`.
+    pub fn message_comment_for_key(&self, key: FrameKey) -> Option<String> {
+        let frame: &Frame = self.frames.get(key)?;
+        let db: &DatabaseDBC = self.get_database_by_channel(frame.channel)?;
+        Some(db.get_message_by_key(frame.msg_key)?.comment.clone())
+    }
+
+    /// Return the payload of the frame `key`, see `Frame::payload_bytes`.
+    pub fn payload_bytes_for_key(&self, key: FrameKey) -> Option<Vec<u8>> {
+        Some(self.frames.get(key)?.payload_bytes())
+    }
+
+    /// Return the payload of the frame `key` as written in the file (`Frame::data`).
+    pub fn payload_bytes_hex_for_key(&self, key: FrameKey) -> Option<String> {
+        Some(self.frames.get(key)?.data.clone())
+    }
+
+    /// Return a copy of the frame `key`.
+    pub fn clone_frame(&self, key: FrameKey) -> Option<Frame> {
+        self.frames.get(key).cloned()
+    }
+
+    /// Return a copy of the frame `key` moved to `new_ts`, with `absolute_time`
+    /// recomputed from `Log::absolute_time` as done by the parser.
+    pub fn clone_frame_at_timestamp(&self, key: FrameKey, new_ts: f64) -> Option<Frame> {
+        let mut frame: Frame = self.clone_frame(key)?;
+        frame.timestamp = new_ts;
+        frame.absolute_time = line::frame_absolute_time(self.absolute_time.value, new_ts);
+        Some(frame)
+    }
+
+    /// Return the raw (unscaled) value of the signal `sig_key` in the frame
+    /// `frame_key`, extracted again from its payload.
+    ///
+    /// `None` if the frame is missing or does not carry the signal in the database
+    /// of its channel.
+    pub fn get_raw_signal_value_at(&self, frame_key: FrameKey, sig_key: SignalKey) -> Option<i64> {
+        let frame: &Frame = self.frames.get(frame_key)?;
+        if !frame.sig_keys.contains(&sig_key) {
+            return None;
+        }
+        let signal = self
+            .get_database_by_channel(frame.channel)?
+            .get_sig_by_key(sig_key)?;
+        let payload: Vec<u8> = self.payload_bytes_for_key(frame_key)?;
+        Some(signal.extract_raw_i64(&payload))
+    }
+
+    /// Return the sorted view matching `view`.
+    pub fn view(&self, view: FrameView) -> &[FrameKey] {
+        match view {
+            FrameView::FileOrder => &self.frame_by_file_order,
+            FrameView::Timestamp => &self.frame_by_timestamp,
+            FrameView::BlockTimestamp => &self.frame_by_block_timestamp,
+            FrameView::Channel => &self.frame_by_channel,
+            FrameView::Direction => &self.frame_by_direction,
+            FrameView::CanMsgName => &self.frame_by_can_msg_name,
+            FrameView::CanSignalName => &self.frame_by_can_signal_name,
+            FrameView::CanMsgId => &self.frame_by_can_msg_id,
+            FrameView::CanDlc => &self.frame_by_can_dlc,
+            FrameView::CanProtocol => &self.frame_by_can_protocol,
+            FrameView::Protocol => &self.frame_by_protocol,
+            FrameView::CanPriority => &self.frame_by_can_priority,
+            FrameView::CanCyclicOnly => &self.frame_by_can_cyclic_only,
+            FrameView::CanEventDrivenOnly => &self.frame_by_can_event_driven_only,
+            FrameView::CanSenderNode => &self.frame_by_can_sender_node,
+            FrameView::CanData => &self.frame_by_can_data,
+            FrameView::CanComment => &self.frame_by_can_comment,
+            FrameView::IdChnTimestamp => &self.id_chn_by_timestamp,
+            FrameView::IdChnChannel => &self.id_chn_by_channel,
+            FrameView::IdChnDirection => &self.id_chn_by_direction,
+            FrameView::IdChnCanMsgName => &self.id_chn_by_can_msg_name,
+            FrameView::IdChnCanMsgId => &self.id_chn_by_can_msg_id,
+            FrameView::IdChnCanDlc => &self.id_chn_by_can_dlc,
+            FrameView::IdChnCanProtocol => &self.id_chn_by_can_protocol,
+            FrameView::IdChnCanSenderNode => &self.id_chn_by_can_sender_node,
+            FrameView::IdChnCanData => &self.id_chn_by_can_data,
+            FrameView::IdChnCanComment => &self.id_chn_by_can_comment,
+        }
+    }
+
+    /// Iterate the frames of `view` lazily as `(key, frame)` pairs, skipping stale keys.
+    pub fn iter_by_view(&self, view: FrameView) -> impl Iterator<Item = (FrameKey, &Frame)> + '_ {
+        self.view(view)
+            .iter()
+            .filter_map(|&key| self.frames.get(key).map(|frame| (key, frame)))
+    }
+
+    /// Iterate the unique `(id, channel)` pairs of CAN frames, in the order of
+    /// `id_chn_by_timestamp`.
+    pub fn iter_id_channel_pairs(&self) -> impl Iterator<Item = (u32, u8)> + '_ {
+        self.id_chn_by_timestamp
+            .iter()
+            .filter_map(|key| self.frames.get(*key))
+            .map(|frame| (frame.id, frame.channel))
+    }
+
+    /// Number of unique `(id, channel)` pairs of CAN frames.
+    pub fn count_unique_id_channel_pairs(&self) -> usize {
+        self.id_chn_by_timestamp.len()
+    }
+
+    /// Return the `page`-th slice (0-based) of at most `page_size` keys of `view`.
+    /// Pages beyond the last one, or a `page_size` of 0, give an empty slice.
+    pub fn paginate_frames(&self, page: usize, page_size: usize, view: FrameView) -> &[FrameKey] {
+        let keys: &[FrameKey] = self.view(view);
+        let start: usize = page.saturating_mul(page_size);
+        if page_size == 0 || start >= keys.len() {
+            return &[];
+        }
+        let end: usize = start.saturating_add(page_size).min(keys.len());
+        &keys[start..end]
+    }
+
+    /// Return the number of pages of `page_size` keys needed to show `view`.
+    pub fn page_count(&self, page_size: usize, view: FrameView) -> usize {
+        if page_size == 0 {
+            return 0;
+        }
+        self.view(view).len().div_ceil(page_size)
+    }
+
+    /// Attach `db` to `channel` and decode the frames already parsed on it.
+    ///
+    /// The channel is created as a CAN channel if missing. Every CAN and remote frame
+    /// of the channel gets its `msg_key` and `tx_node_key` resolved again, CAN frames
+    /// also their `sig_keys`. The decoded values are appended to the signal time
+    /// series of `db`, and all the sorted views are rebuilt since several of them
+    /// depend on the database.
+    pub fn apply_dbc_to_channel(&mut self, channel: u8, db: DatabaseDBC) {
+        self.channel_map
+            .entry(channel)
+            .or_insert_with(|| ChannelInfo {
+                number: channel,
+                ..ChannelInfo::default()
+            })
+            .database = Some(db);
+        // frames already saved are decoded again against the new database
+        self.reset_checkpoint_cursor();
+
+        let Log {
+            frames,
+            channel_map,
+            frame_by_file_order,
+            trigger_blocks,
+            ..
+        } = &mut *self;
+        if let Some(dbc) = channel_map
+            .get_mut(&channel)
+            .and_then(|ch_info| ch_info.database.as_mut())
+        {
+            for key in frame_by_file_order.iter() {
+                let Some(frame) = frames.get_mut(*key) else {
+                    continue;
+                };
+                if frame.channel != channel {
+                    continue;
+                }
+                if frame.ftype == FrameType::Remote {
+                    frame.msg_key = MessageKey::default();
+                    frame.tx_node_key = NodeKey::default();
+                    line::resolve_remote_frame(dbc, frame);
+                    continue;
+                }
+                if frame.ftype != FrameType::Can {
+                    continue;
+                }
+                frame.msg_key = MessageKey::default();
+                frame.tx_node_key = NodeKey::default();
+                frame.sig_keys.clear();
+
+                let payload: Vec<u8> = frame.payload_bytes();
+                let timestamp: f64 = trigger::block_adjusted_timestamp(trigger_blocks, frame);
+                line::decode_with_database(dbc, frame, &payload, timestamp);
+            }
+        }
+
+        sort::rebuild_views(self);
+    }
+
+    /// Encode physical signal values into a copy of the frame at `frame_key`.
+    ///
+    /// Each value is converted back to raw with `raw = (physical - offset) / factor`
+    /// (rounded) and packed into the payload at the signal position. The bit layout
+    /// is derived from `extract_raw_i64` by probing one payload bit at a time, so it
+    /// follows the database decoding exactly (byte order and sign included).
+    /// Signals not carried by the frame or with a zero factor are ignored; payload
+    /// bits not covered by the given signals keep their value.
+    ///
+    /// Returns `None` if the frame is unknown or its message is not in the database.
+    pub fn encode_frame(
+        &self,
+        frame_key: FrameKey,
+        signals: &HashMap<SignalKey, f64>,
+    ) -> Option<Frame> {
+        let frame: &Frame = self.frames.get(frame_key)?;
+        let db: &DatabaseDBC = self.get_database_by_channel(frame.channel)?;
+        db.get_message_by_key(frame.msg_key)?;
+
+        let mut payload: Vec<u8> = frame.payload_bytes();
+        let mut probe: Vec<u8> = vec![0; payload.len()];
+        for (&sig_key, &physical) in signals {
+            if !frame.sig_keys.contains(&sig_key) {
+                continue;
+            }
+            let Some(signal) = db.get_sig_by_key(sig_key) else {
+                continue;
+            };
+            if signal.factor == 0.0 {
+                continue;
+            }
+            let raw: i64 = ((physical - signal.offset) / signal.factor).round() as i64;
+
+            for bit in 0..payload.len() * 8 {
+                let mask: u8 = 1 << (bit % 8);
+                probe[bit / 8] = mask;
+                let probed: i64 = signal.extract_raw_i64(&probe);
+                probe[bit / 8] = 0;
+                if probed == 0 {
+                    continue; // bit not part of the signal
+                }
+                // lowest set bit, also correct for a sign-extended sign bit
+                let raw_bit: u32 = probed.trailing_zeros();
+                if (raw >> raw_bit) & 1 == 1 {
+                    payload[bit / 8] |= mask;
+                } else {
+                    payload[bit / 8] &= !mask;
+                }
+            }
+        }
+
+        let mut encoded: Frame = frame.clone();
+        encoded.set_payload(&payload);
+        Some(encoded)
+    }
+
+    /// Estimate the bit error rate of a channel from the density of error frames.
+    ///
+    /// This is a proxy metric, not a physical-layer measurement:
+    /// - every `FrameType::ErrorFrame` counts as one erroneous bit;
+    /// - the bits transmitted are estimated as the sum of the data frame lengths in
+    ///   bits, i.e. frame count × average length (see `Frame::estimated_bit_count`,
+    ///   bit stuffing ignored);
+    /// - the estimate is capped by the bus capacity over the observed window,
+    ///   i.e. duration × `ChannelInfo::bit_rate` (500 kbit/s when unset).
+    ///
+    /// Returns `None` if the channel has no data frames.
+    pub fn compute_bit_error_rate(&self, channel: u8) -> Option<f64> {
+        let mut error_frames: usize = 0;
+        let mut data_frames: usize = 0;
+        let mut data_bits: u64 = 0;
+        let mut first_ts: Option<f64> = None;
+        let mut last_ts: f64 = 0.0;
+
+        for key in &self.frame_by_file_order {
+            let Some(frame) = self.frames.get(*key) else {
+                continue;
+            };
+            if frame.channel != channel {
+                continue;
+            }
+            let timestamp: f64 = self.block_adjusted_timestamp(frame);
+            first_ts.get_or_insert(timestamp);
+            last_ts = last_ts.max(timestamp);
+            match frame.ftype {
+                FrameType::ErrorFrame => error_frames += 1,
+                FrameType::Can => {
+                    data_frames += 1;
+                    data_bits += u64::from(frame.estimated_bit_count());
+                }
+                _ => {}
+            }
+        }
+
+        if data_frames == 0 {
+            return None;
+        }
+
+        let mut total_bits: f64 = data_bits as f64;
+
+        let bit_rate: u32 = self
+            .channel_map
+            .get(&channel)
+            .map(|info| info.bit_rate_or_default())
+            .unwrap_or(DEFAULT_BIT_RATE);
+        let duration: f64 = last_ts - first_ts.unwrap_or(last_ts);
+        if duration > 0.0 {
+            total_bits = total_bits.min(duration * f64::from(bit_rate));
+        }
+
+        Some(error_frames as f64 / total_bits)
+    }
+
+    /// Bus load of `channel` in consecutive, non-overlapping bins of `interval_ms`,
+    /// as `(bin_center_ts, load_fraction)`.
+    ///
+    /// Bins are aligned on multiples of `interval_ms` and span from the first to the
+    /// last frame of the channel. Each frame occupies the bus, ending at its
+    /// timestamp, at `ChannelInfo::bit_rate` (500 kbit/s when unset) for:
+    /// - CAN and CAN-FD frames: `Frame::estimated_bit_count` bits, the CAN-FD data
+    ///   phase is counted at the nominal bit rate;
+    /// - remote frames: their overhead bits, they carry no data;
+    /// - error frames: `ERROR_FRAME_BIT_COUNT` bits.
+    ///
+    /// A frame crossing a bin boundary is split between both bins. Bins without
+    /// frames have a load of 0.0, loads are capped at 1.0. Returns an empty `Vec` if
+    /// `interval_ms` is 0 or the channel has no frames.
+    pub fn compute_busload_per_interval(&self, channel: u8, interval_ms: u64) -> Vec<(f64, f64)> {
+        if interval_ms == 0 {
+            return Vec::new();
+        }
+        let interval_s: f64 = interval_ms as f64 / 1000.0;
+        let bit_rate: f64 = f64::from(
+            self.channel_map
+                .get(&channel)
+                .map(|info| info.bit_rate_or_default())
+                .unwrap_or(DEFAULT_BIT_RATE),
+        );
+
+        // busy time per bin, starting from bin `first_bin`
+        let mut first_bin: Option<i64> = None;
+        let mut busy: Vec<f64> = Vec::new();
+        for key in &self.frame_by_timestamp {
+            let Some(frame) = self.frames.get(*key) else {
+                continue;
+            };
+            if frame.channel != channel {
+                continue;
+            }
+            let bits: u32 = match frame.ftype {
+                FrameType::Can | FrameType::Remote => frame.estimated_bit_count(),
+                FrameType::ErrorFrame => ERROR_FRAME_BIT_COUNT,
+                FrameType::Eth => continue,
+            };
+            let end: f64 = self.block_adjusted_timestamp(frame);
+            let start: f64 = end - f64::from(bits) / bit_rate;
+            let base: i64 = *first_bin.get_or_insert((start / interval_s).floor() as i64);
+
+            let mut bin: i64 = ((start / interval_s).floor() as i64).max(base);
+            loop {
+                let bin_start: f64 = bin as f64 * interval_s;
+                let bin_end: f64 = bin_start + interval_s;
+                let overlap: f64 = end.min(bin_end) - start.max(bin_start);
+                let index: usize = (bin - base) as usize;
+                if busy.len() <= index {
+                    busy.resize(index + 1, 0.0);
+                }
+                if overlap > 0.0 {
+                    busy[index] += overlap;
+                }
+                if end <= bin_end {
+                    break;
+                }
+                bin += 1;
+            }
+        }
+
+        let Some(base) = first_bin else {
+            return Vec::new();
+        };
+        busy.into_iter()
+            .enumerate()
+            .map(|(i, busy_s)| {
+                let center: f64 = ((base + i as i64) as f64 + 0.5) * interval_s;
+                (center, (busy_s / interval_s).min(1.0))
+            })
+            .collect()
+    }
+
+    /// Number of frames of `channel` per time bucket of `bucket_ms` milliseconds, as
+    /// `(bucket_start_ts, frame_count)` for the non-empty buckets in time order.
+    ///
+    /// Buckets are aligned on multiples of `bucket_ms`. Single scan of
+    /// `frame_by_timestamp`.
+    ///
+    /// # Panics
+    /// Panics if `bucket_ms` is 0.
+    pub fn temporal_density(&self, channel: u8, bucket_ms: u64) -> Vec<(f64, usize)> {
+        assert!(
+            bucket_ms > 0,
+            "temporal_density: bucket_ms must be greater than 0"
+        );
+        let bucket_s: f64 = bucket_ms as f64 / 1000.0;
+
+        let mut density: Vec<(f64, usize)> = Vec::new();
+        let mut current: Option<i64> = None;
+        for key in &self.frame_by_timestamp {
+            let Some(frame) = self.frames.get(*key) else {
+                continue;
+            };
+            if frame.channel != channel {
+                continue;
+            }
+            let bucket: i64 = (self.block_adjusted_timestamp(frame) / bucket_s).floor() as i64;
+            match density.last_mut() {
+                Some((_, count)) if current == Some(bucket) => *count += 1,
+                _ => {
+                    density.push((bucket as f64 * bucket_s, 1));
+                    current = Some(bucket);
+                }
+            }
+        }
+        density
+    }
+
+    /// Average frames per second on `channel`: frame count divided by the time span
+    /// between its first and last frame. `None` with fewer than 2 frames or a zero span.
+    pub fn channel_frame_rate(&self, channel: u8) -> Option<f64> {
+        let mut count: usize = 0;
+        let mut first: f64 = f64::INFINITY;
+        let mut last: f64 = f64::NEG_INFINITY;
+        for frame in self
+            .frames
+            .values()
+            .filter(|frame| frame.channel == channel)
+        {
+            let timestamp: f64 = self.block_adjusted_timestamp(frame);
+            count += 1;
+            first = first.min(timestamp);
+            last = last.max(timestamp);
+        }
+        frame_rate(count, first, last)
+    }
+
+    /// `channel_frame_rate` of every channel with frames, computed in a single pass.
+    /// Channels without a valid rate are left out.
+    pub fn channel_frame_rates(&self) -> HashMap<u8, f64> {
+        let mut spans: HashMap<u8, (usize, f64, f64)> = HashMap::new();
+        for frame in self.frames.values() {
+            let (count, first, last) =
+                spans
+                    .entry(frame.channel)
+                    .or_insert((0, f64::INFINITY, f64::NEG_INFINITY));
+            let timestamp: f64 = self.block_adjusted_timestamp(frame);
+            *count += 1;
+            *first = first.min(timestamp);
+            *last = last.max(timestamp);
+        }
+        spans
+            .into_iter()
+            .filter_map(|(channel, (count, first, last))| {
+                frame_rate(count, first, last).map(|rate| (channel, rate))
+            })
+            .collect()
+    }
+
+    /// Return the last CAN frame, in file order, of `(id, channel)`.
+    ///
+    /// Looked up in an index of `id_chn_by_timestamp` built on first use and cached
+    /// until the frames change.
+    pub fn most_recent_frame_by_id(&self, id: u32, channel: u8) -> Option<&Frame> {
+        let index: &HashMap<(u32, u8), FrameKey> = self.id_chn_index.get_or_init(|| {
+            self.id_chn_by_timestamp
+                .iter()
+                .filter_map(|key| {
+                    let frame: &Frame = self.frames.get(*key)?;
+                    Some(((frame.id, frame.channel), *key))
+                })
+                .collect()
+        });
+        self.frames.get(*index.get(&(id, channel))?)
+    }
+
+    /// Frame count and cycle time statistics of every CAN `(id, channel)` pair,
+    /// sorted by `(channel, id)`.
+    pub fn compute_message_statistics(&self) -> Vec<MessageStats> {
+        let mut stats: HashMap<(u32, u8), (MessageStats, f64)> = HashMap::new();
+        for key in &self.frame_by_timestamp {
+            let Some(frame) = self.frames.get(*key) else {
+                continue;
+            };
+            if frame.ftype != FrameType::Can {
+                continue;
+            }
+            let timestamp: f64 = self.block_adjusted_timestamp(frame);
+            let (entry, cycle_sum_ms) =
+                stats.entry((frame.id, frame.channel)).or_insert_with(|| {
+                    let name: String = self
+                        .get_database_by_channel(frame.channel)
+                        .map(|db| frame.msg_name_to_string(db))
+                        .unwrap_or_default();
+                    let entry = MessageStats {
+                        id: frame.id_hex.clone(),
+                        channel: frame.channel,
+                        name,
+                        first_ts: timestamp,
+                        last_ts: timestamp,
+                        min_cycle_ms: f64::INFINITY,
+                        max_cycle_ms: f64::NEG_INFINITY,
+                        ..MessageStats::default()
+                    };
+                    (entry, 0.0)
+                });
+            if entry.frame_count > 0 {
+                let cycle_ms: f64 = (timestamp - entry.last_ts) * 1000.0;
+                *cycle_sum_ms += cycle_ms;
+                entry.min_cycle_ms = entry.min_cycle_ms.min(cycle_ms);
+                entry.max_cycle_ms = entry.max_cycle_ms.max(cycle_ms);
+            }
+            entry.frame_count += 1;
+            entry.last_ts = timestamp;
+        }
+
+        let mut result: Vec<((u32, u8), MessageStats)> = stats
+            .into_iter()
+            .map(|(pair, (mut entry, cycle_sum_ms))| {
+                if entry.frame_count < 2 {
+                    entry.mean_cycle_ms = f64::NAN;
+                    entry.min_cycle_ms = f64::NAN;
+                    entry.max_cycle_ms = f64::NAN;
+                } else {
+                    entry.mean_cycle_ms = cycle_sum_ms / (entry.frame_count - 1) as f64;
+                }
+                (pair, entry)
+            })
+            .collect();
+        result.sort_by_key(|((id, channel), _)| (*channel, *id));
+        result.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Return `(id, channel, frames_per_second)` for every CAN `(id, channel)` pair
+    /// sent faster than `threshold_fps`, fastest first.
+    ///
+    /// The rate of a pair is computed as in `channel_frame_rate`, pairs with fewer
+    /// than 2 frames or a zero time span are never reported.
+    pub fn detect_babbling_idiot(&self, threshold_fps: f64) -> Vec<(u32, u8, f64)> {
+        let mut spans: HashMap<(u32, u8), (usize, f64, f64)> = HashMap::new();
+        for frame in self.frames.values() {
+            if frame.ftype != FrameType::Can {
+                continue;
+            }
+            let (count, first, last) = spans.entry((frame.id, frame.channel)).or_insert((
+                0,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+            ));
+            let timestamp: f64 = self.block_adjusted_timestamp(frame);
+            *count += 1;
+            *first = first.min(timestamp);
+            *last = last.max(timestamp);
+        }
+
+        let mut result: Vec<(u32, u8, f64)> = spans
+            .into_iter()
+            .filter_map(|((id, channel), (count, first, last))| {
+                frame_rate(count, first, last)
+                    .filter(|rate| *rate > threshold_fps)
+                    .map(|rate| (id, channel, rate))
+            })
+            .collect();
+        result.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.1.cmp(&b.1)).then(a.0.cmp(&b.0)));
+        result
+    }
+
+    /// Iterate the CAN frames of `(id, channel)` in timestamp order.
+    pub(crate) fn iter_id_channel_frames(
+        &self,
+        id: u32,
+        channel: u8,
+    ) -> impl Iterator<Item = (FrameKey, &Frame)> + '_ {
+        self.frame_by_timestamp.iter().filter_map(move |&key| {
+            self.frames
+                .get(key)
+                .filter(|frame| {
+                    frame.ftype == FrameType::Can && frame.id == id && frame.channel == channel
+                })
+                .map(|frame| (key, frame))
+        })
+    }
+
+    /// Timestamp of the first CAN frame of `(id, channel)`, `None` if the pair is absent.
+    pub fn time_of_first_frame_by_id(&self, id: u32, channel: u8) -> Option<f64> {
+        self.timestamps_by_id(id, channel).min_by(f64::total_cmp)
+    }
+
+    /// Timestamp of the last CAN frame of `(id, channel)`, `None` if the pair is absent.
+    pub fn time_of_last_frame_by_id(&self, id: u32, channel: u8) -> Option<f64> {
+        self.timestamps_by_id(id, channel).max_by(f64::total_cmp)
+    }
+
+    /// Timestamps of the CAN frames of `(id, channel)`, found by binary search of the
+    /// id range in `frame_by_can_msg_id` followed by a linear scan for the channel.
+    fn timestamps_by_id(&self, id: u32, channel: u8) -> impl Iterator<Item = f64> + '_ {
+        let frame_id = |key: &FrameKey| self.frames.get(*key).map_or(u32::MAX, |frame| frame.id);
+        let start: usize = self
+            .frame_by_can_msg_id
+            .partition_point(|key| frame_id(key) < id);
+        let end: usize = self
+            .frame_by_can_msg_id
+            .partition_point(|key| frame_id(key) <= id);
+        self.frame_by_can_msg_id[start..end]
+            .iter()
+            .filter_map(|key| self.frames.get(*key))
+            .filter(move |frame| frame.ftype == FrameType::Can && frame.channel == channel)
+            .map(|frame| self.block_adjusted_timestamp(frame))
+    }
+
+    /// Sample the CAN frames of `(id, channel)` on a regular time grid.
+    ///
+    /// The grid starts at the first frame timestamp and advances by `interval_s`
+    /// up to the last frame timestamp. Each grid point is paired with the most
+    /// recent frame at or before it. Returns an empty `Vec` if `interval_s <= 0`
+    /// or no frame matches.
+    pub fn sample_at_interval(&self, channel: u8, id: u32, interval_s: f64) -> Vec<(f64, &Frame)> {
+        let frames: Vec<(f64, &Frame)> = self
+            .iter_id_channel_frames(id, channel)
+            .map(|(_, frame)| (self.block_adjusted_timestamp(frame), frame))
+            .collect();
+        let (Some(&(first, _)), Some(&(last, _))) = (frames.first(), frames.last()) else {
+            return Vec::new();
+        };
+        let len: usize = resample::grid_len(first, last, interval_s);
+
+        let mut samples: Vec<(f64, &Frame)> = Vec::with_capacity(len);
+        let mut next: usize = 0;
+        for i in 0..len {
+            let t: f64 = first + i as f64 * interval_s;
+            while next < frames.len() && frames[next].0 <= t {
+                next += 1;
+            }
+            if next > 0 {
+                samples.push((t, frames[next - 1].1));
+            }
+        }
+        samples
+    }
+
+    /// Build a synthetic `Log` where every CAN `(id, channel)` pair is sent exactly
+    /// every `interval_s` seconds.
+    ///
+    /// For each pair, frames are generated on the grid `first + i * interval_s` up to
+    /// its last timestamp, each one a copy of the most recent actual frame (zero-order
+    /// hold) with the grid timestamp. `channel_map` and `absolute_time` are copied,
+    /// the signal time series of the databases are rebuilt from the synthetic
+    /// frames, and all the sorted views are built. The file order is the timestamp
+    /// order. Returns a `Log` without frames if `interval_s <= 0`.
+    pub fn resample_to_log(&self, interval_s: f64) -> Log {
+        let mut log: Log = Log {
+            channel_map: self.channel_map.clone(),
+            absolute_time: self.absolute_time.clone(),
+            ..Log::default()
+        };
+        for (channel, sig_key) in self.observed_signals() {
+            if let Some(signal) = log
+                .get_mut_database_by_channel(channel)
+                .and_then(|db| db.get_sig_by_key_mut(sig_key))
+            {
+                signal.values.clear();
+                signal.raws.clear();
+            }
+        }
+
+        let mut pairs: Vec<(u32, u8)> = self
+            .id_chn_by_timestamp
+            .iter()
+            .filter_map(|key| self.frames.get(*key))
+            .map(|frame| (frame.id, frame.channel))
+            .collect();
+        pairs.sort_unstable();
+
+        let mut synthetic: Vec<Frame> = Vec::new();
+        for (id, channel) in pairs {
+            for (timestamp, frame) in self.sample_at_interval(channel, id, interval_s) {
+                let mut frame: Frame = frame.clone();
+                frame.timestamp = timestamp;
+                frame.trigger_block = None;
+                frame.absolute_time = line::frame_absolute_time(log.absolute_time.value, timestamp);
+                synthetic.push(frame);
+            }
+        }
+        synthetic.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+
+        for mut frame in synthetic {
+            if let Some(db) = log.get_mut_database_by_channel(frame.channel) {
+                let payload: Vec<u8> = frame.payload_bytes();
+                let timestamp: f64 = frame.timestamp;
+                line::decode_with_database(db, &mut frame, &payload, timestamp);
+            }
+            let frame_key: FrameKey = log.frames.insert(frame);
+            log.frame_by_file_order.push(frame_key);
+        }
+        sort::rebuild_views(&mut log);
+        log
+    }
+
+    /// Check the rolling counter of a message for lost or repeated frames.
+    ///
+    /// The counter is read from the lowest `counter_bits` bits of payload byte
+    /// `counter_byte`. Frames of `(id, channel)` are visited in timestamp order and
+    /// every frame whose counter is not the previous one + 1 (mod 2^counter_bits)
+    /// is returned as `(frame_key, expected, actual)`. The sequence resynchronises
+    /// on the actual value after a mismatch. An empty result means no gaps.
+    pub fn frame_sequence_check(
+        &self,
+        id: u32,
+        channel: u8,
+        counter_byte: usize,
+        counter_bits: usize,
+    ) -> Vec<(FrameKey, u8, u8)> {
+        let mut result: Vec<(FrameKey, u8, u8)> = Vec::new();
+        if counter_bits == 0 || counter_bits > 8 {
+            return result;
+        }
+        let modulus: u16 = 1 << counter_bits;
+        let mask: u8 = (modulus - 1) as u8;
+
+        let mut previous: Option<u8> = None;
+        for (key, frame) in self.iter_id_channel_frames(id, channel) {
+            let Some(&byte) = frame.payload_bytes().get(counter_byte) else {
+                continue;
+            };
+            let actual: u8 = byte & mask;
+            if let Some(prev) = previous {
+                let expected: u8 = ((u16::from(prev) + 1) % modulus) as u8;
+                if actual != expected {
+                    result.push((key, expected, actual));
+                }
+            }
+            previous = Some(actual);
+        }
+        result
+    }
+
+    /// Return the frames of `(id, channel)` whose gap to the previous frame lies outside
+    /// `nominal_ms ± tolerance_pct %`. Frames are visited in timestamp order and the
+    /// first frame is never a violation.
+    pub fn frame_cycle_time_violations(
+        &self,
+        id: u32,
+        channel: u8,
+        nominal_ms: f64,
+        tolerance_pct: f64,
+    ) -> Vec<FrameKey> {
+        let min_ms: f64 = nominal_ms * (1.0 - tolerance_pct / 100.0);
+        let max_ms: f64 = nominal_ms * (1.0 + tolerance_pct / 100.0);
+
+        let mut result: Vec<FrameKey> = Vec::new();
+        let mut previous_ts: Option<f64> = None;
+        for (key, frame) in self.iter_id_channel_frames(id, channel) {
+            let timestamp: f64 = self.block_adjusted_timestamp(frame);
+            if let Some(prev) = previous_ts {
+                let gap_ms: f64 = (timestamp - prev) * 1000.0;
+                if gap_ms < min_ms || gap_ms > max_ms {
+                    result.push(key);
+                }
+            }
+            previous_ts = Some(timestamp);
+        }
+        result
+    }
+
+    /// Return `(mean_gap_s, std_dev_gap_s)` of the inter-frame gaps of every CAN
+    /// `(id, channel)` pair, pairs with fewer than 2 frames are omitted.
+    ///
+    /// Single pass over `frame_by_timestamp` with Welford's online algorithm, the
+    /// standard deviation is the population one.
+    pub fn compute_interframe_jitter_map(&self) -> HashMap<(u32, u8), (f64, f64)> {
+        // (last timestamp, gap count, running mean, sum of squared deviations)
+        let mut stats: HashMap<(u32, u8), (f64, usize, f64, f64)> = HashMap::new();
+        for key in &self.frame_by_timestamp {
+            let Some(frame) = self.frames.get(*key) else {
+                continue;
+            };
+            if frame.ftype != FrameType::Can {
+                continue;
+            }
+            let timestamp: f64 = self.block_adjusted_timestamp(frame);
+            match stats.get_mut(&(frame.id, frame.channel)) {
+                Some((last_ts, count, mean, m2)) => {
+                    let gap: f64 = timestamp - *last_ts;
+                    *last_ts = timestamp;
+                    *count += 1;
+                    let delta: f64 = gap - *mean;
+                    *mean += delta / *count as f64;
+                    *m2 += delta * (gap - *mean);
+                }
+                None => {
+                    stats.insert((frame.id, frame.channel), (timestamp, 0, 0.0, 0.0));
+                }
+            }
+        }
+
+        stats
+            .into_iter()
+            .filter(|(_, (_, count, _, _))| *count > 0)
+            .map(|(pair, (_, count, mean, m2))| (pair, (mean, (m2 / count as f64).sqrt())))
+            .collect()
+    }
+
+    /// Shannon entropy, in bits per byte, of all payload bytes of `(id, channel)`.
+    ///
+    /// Values close to 8 hint at encrypted or compressed payloads, values close
+    /// to 0 at constant fields. Returns `None` if no frame matches.
+    pub fn compute_payload_entropy(&self, id: u32, channel: u8) -> Option<f64> {
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut found: bool = false;
+        for (_, frame) in self.iter_id_channel_frames(id, channel) {
+            found = true;
+            bytes.extend(frame.payload_bytes());
+        }
+        found.then(|| entropy::shannon_entropy(&bytes))
+    }
+
+    /// Build, for every channel with a database, the list of nodes with the messages
+    /// they send and receive, sorted by node name.
+    ///
+    /// Every message of the database is considered, ordered by id, whether the
+    /// trace carries it or not. Senders come from the message, receivers from its
+    /// signals. A channel holds one `NodeTopology` per node since a bus connects
+    /// several nodes.
+    pub fn build_topology_map(&self) -> HashMap<u8, Vec<NodeTopology>> {
+        let mut topology: HashMap<u8, Vec<NodeTopology>> = HashMap::new();
+        for (&channel, info) in &self.channel_map {
+            let Some(db) = info.database.as_ref() else {
+                continue;
+            };
+            let mut messages: Vec<_> = db.messages.iter().collect();
+            messages.sort_by_key(|(_, msg)| msg.id);
+
+            let mut nodes: HashMap<NodeKey, NodeTopology> = HashMap::new();
+            for (msg_key, msg) in messages {
+                for &node_key in &msg.sender_nodes {
+                    if let Some(entry) = topology_node(&mut nodes, db, node_key) {
+                        entry.tx_messages.push(msg_key);
+                    }
+                }
+                let mut receivers: Vec<NodeKey> = msg
+                    .signals
+                    .iter()
+                    .filter_map(|sig_key| db.get_sig_by_key(*sig_key))
+                    .flat_map(|signal| signal.receiver_nodes.iter().copied())
+                    .collect();
+                receivers.sort_unstable();
+                receivers.dedup();
+                for node_key in receivers {
+                    if let Some(entry) = topology_node(&mut nodes, db, node_key) {
+                        entry.rx_messages.push(msg_key);
+                    }
+                }
+            }
+            let mut nodes: Vec<NodeTopology> = nodes.into_values().collect();
+            nodes.sort_by(|a, b| a.node_name.cmp(&b.node_name));
+            topology.insert(channel, nodes);
+        }
+        topology
+    }
+
+    /// Fraction of consecutive CAN frame pairs of `(id, channel)`, in timestamp order,
+    /// whose `data` differ: 0.0 for a constant payload, 1.0 when every frame brings
+    /// a new one. Returns `None` with fewer than 2 frames.
+    pub fn compute_payload_change_rate(&self, id: u32, channel: u8) -> Option<f64> {
+        let mut pairs: usize = 0;
+        let mut changes: usize = 0;
+        let mut previous: Option<&str> = None;
+        for (_, frame) in self.iter_id_channel_frames(id, channel) {
+            if let Some(previous) = previous {
+                pairs += 1;
+                if previous != frame.data {
+                    changes += 1;
+                }
+            }
+            previous = Some(&frame.data);
+        }
+        (pairs > 0).then(|| changes as f64 / pairs as f64)
+    }
+
+    /// Bitwise Hamming distance between the payloads of consecutive CAN frames of
+    /// `(id, channel)`, as `(previous_key, key, distance)` in timestamp order.
+    /// Bytes missing in the shorter payload count as `0x00`.
+    pub fn compute_hamming_distances(
+        &self,
+        id: u32,
+        channel: u8,
+    ) -> Vec<(FrameKey, FrameKey, u32)> {
+        let payloads: Vec<(FrameKey, Vec<u8>)> = self
+            .iter_id_channel_frames(id, channel)
+            .map(|(key, frame)| (key, frame.payload_bytes()))
+            .collect();
+        payloads
+            .windows(2)
+            .map(|pair| {
+                (
+                    pair[0].0,
+                    pair[1].0,
+                    hamming_distance(&pair[0].1, &pair[1].1),
+                )
+            })
+            .collect()
+    }
+
+    /// Return the frames of the same `(id, channel)` as `reference_key` whose payload
+    /// is at most `max_distance` bits away from the reference, in timestamp order.
+    /// The reference itself is left out; an unknown key gives an empty `Vec`.
+    pub fn frames_within_hamming_distance(
+        &self,
+        reference_key: FrameKey,
+        max_distance: u32,
+    ) -> Vec<FrameKey> {
+        let Some(reference) = self.frames.get(reference_key) else {
+            return Vec::new();
+        };
+        let reference_payload: Vec<u8> = reference.payload_bytes();
+        self.iter_id_channel_frames(reference.id, reference.channel)
+            .filter(|(key, frame)| {
+                *key != reference_key
+                    && hamming_distance(&reference_payload, &frame.payload_bytes()) <= max_distance
+            })
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Return the CAN frames, in file order, whose payload byte `byte_index` equals `value`.
+    pub fn frame_by_can_byte_value(&self, byte_index: usize, value: u8) -> Vec<FrameKey> {
+        self.frame_by_can_byte_range(byte_index, value, value)
+    }
+
+    /// Return the CAN frames, in file order, whose payload byte `byte_index` lies in `[min, max]`.
+    /// Frames shorter than `byte_index + 1` bytes never match.
+    pub fn frame_by_can_byte_range(&self, byte_index: usize, min: u8, max: u8) -> Vec<FrameKey> {
+        self.frame_by_file_order
+            .iter()
+            .copied()
+            .filter(|key| {
+                let Some(frame) = self.frames.get(*key) else {
+                    return false;
+                };
+                if frame.ftype != FrameType::Can || byte_index >= frame.byte_length as usize {
+                    return false;
+                }
+                frame
+                    .data
+                    .split_ascii_whitespace()
+                    .nth(byte_index)
+                    .and_then(|tok| u8::from_str_radix(tok, 16).ok())
+                    .is_some_and(|byte| (min..=max).contains(&byte))
+            })
+            .collect()
+    }
+
+    /// Count the CAN frames of every `(id, channel)` pair.
+    pub fn build_frequency_map(&self) -> HashMap<(u32, u8), usize> {
+        let mut counts: HashMap<(u32, u8), usize> = HashMap::new();
+        for frame in self.frames.values() {
+            if frame.ftype == FrameType::Can {
+                *counts.entry((frame.id, frame.channel)).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Return the result of `build_frequency_map`, computed on the first call and
+    /// cached until `invalidate_frequency_cache` is called.
+    ///
+    /// Parsing, importing and the methods of `Log` that add or remove frames drop
+    /// the cache. `frames` and the views are public though: callers changing them
+    /// directly must call `invalidate_frequency_cache`, or get stale counts.
+    pub fn get_or_build_frequency_cache(&self) -> &HashMap<(u32, u8), usize> {
+        self.frequency_cache
+            .get_or_init(|| self.build_frequency_map())
+    }
+
+    /// Return the `n` most frequent `(id, channel, count)` triples of CAN frames,
+    /// sorted by count descending and then by id ascending.
+    pub fn top_n_ids_by_frequency(&self, n: usize) -> Vec<(u32, u8, usize)> {
+        self.top_n_ids_by(n, |_| 1)
+    }
+
+    /// Same as `top_n_ids_by_frequency`, but weighted by `byte_length` so the
+    /// result ranks ids by payload bandwidth instead of frame count.
+    pub fn top_n_ids_by_byte_volume(&self, n: usize) -> Vec<(u32, u8, usize)> {
+        self.top_n_ids_by(n, |frame| frame.byte_length as usize)
+    }
+
+    /// Total payload bytes of the CAN frames.
+    pub fn total_data_bytes(&self) -> u64 {
+        self.data_bytes_where(|_| true)
+    }
+
+    /// Payload bytes of the CAN frames of `channel`.
+    pub fn data_bytes_for_channel(&self, channel: u8) -> u64 {
+        self.data_bytes_where(|frame| frame.channel == channel)
+    }
+
+    /// Payload bytes of the CAN frames of `(id, channel)`.
+    pub fn data_bytes_for_id(&self, id: u32, channel: u8) -> u64 {
+        self.data_bytes_where(|frame| frame.id == id && frame.channel == channel)
+    }
+
+    fn data_bytes_where<F>(&self, predicate: F) -> u64
+    where
+        F: Fn(&Frame) -> bool,
+    {
+        self.frames
+            .values()
+            .filter(|frame| frame.ftype == FrameType::Can && predicate(frame))
+            .map(|frame| u64::from(frame.byte_length))
+            .sum()
+    }
+
+    fn top_n_ids_by<F>(&self, n: usize, weight: F) -> Vec<(u32, u8, usize)>
+    where
+        F: Fn(&Frame) -> usize,
+    {
+        let mut counts: HashMap<(u32, u8), usize> = HashMap::new();
+        for frame in self.frames.values() {
+            if frame.ftype == FrameType::Can {
+                *counts.entry((frame.id, frame.channel)).or_insert(0) += weight(frame);
+            }
+        }
+
+        let mut result: Vec<(u32, u8, usize)> = counts
+            .into_iter()
+            .map(|((id, channel), count)| (id, channel, count))
+            .collect();
+        let order = |a: &(u32, u8, usize), b: &(u32, u8, usize)| {
+            b.2.cmp(&a.2).then(a.0.cmp(&b.0)).then(a.1.cmp(&b.1))
+        };
+        if n < result.len() {
+            if n == 0 {
+                return Vec::new();
+            }
+            result.select_nth_unstable_by(n - 1, order);
+            result.truncate(n);
+        }
+        result.sort_by(order);
+        result
+    }
+
+    /// Case-insensitive substring search over `id_hex`, `data` and, when the channel
+    /// has a database, message name, sender node name and message comment.
+    /// Results are in file order.
+    pub fn search_frames(&self, query: &str) -> Vec<FrameKey> {
+        let query: String = query.to_lowercase();
+        self.search_frames_by(|field| field.to_lowercase().contains(&query))
+    }
+
+    /// Same as `search_frames`, but every field is matched against a regular expression.
+    #[cfg(feature = "regex")]
+    pub fn search_frames_regex(&self, pattern: &str) -> Result<Vec<FrameKey>, regex::Error> {
+        let re: regex::Regex = regex::Regex::new(pattern)?;
+        Ok(self.search_frames_by(|field| re.is_match(field)))
+    }
+
+    fn search_frames_by<F>(&self, matches: F) -> Vec<FrameKey>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let frame_matches = |frame: &Frame| -> bool {
+            if matches(&frame.id_hex) || matches(&frame.data) {
+                return true;
+            }
+            let Some(db) = self.get_database_by_channel(frame.channel) else {
+                return false;
+            };
+            if let Some(msg) = db.get_message_by_key(frame.msg_key)
+                && (matches(&msg.name) || matches(&msg.comment))
+            {
+                return true;
+            }
+            db.get_node_by_key(frame.tx_node_key)
+                .is_some_and(|node| matches(&node.name))
+        };
+
+        self.find_frames_matching(frame_matches)
+    }
+
+    /// Return the frames, in file order, for which `predicate` returns `true`.
+    pub fn find_frames_matching<P>(&self, predicate: P) -> Vec<FrameKey>
+    where
+        P: Fn(&Frame) -> bool,
+    {
+        self.filter_frames(predicate).collect()
+    }
+
+    /// Lazy variant of `find_frames_matching`, for callers that only need the first matches.
+    pub fn filter_frames<'a, P>(&'a self, predicate: P) -> impl Iterator<Item = FrameKey> + 'a
+    where
+        P: Fn(&Frame) -> bool + 'a,
+    {
+        self.frame_by_file_order
+            .iter()
+            .copied()
+            .filter(move |key| self.frames.get(*key).is_some_and(&predicate))
+    }
+
+    /// Return `(id, channel, dlc_a, dlc_b)` for every CAN `(id, channel)` pair seen
+    /// with more than one `byte_length`. `dlc_a` is the first length in file order,
+    /// `dlc_b` the first different one. One entry per pair, sorted by `(channel, id)`.
+    pub fn detect_duplicate_ids_different_dlc(&self) -> Vec<(u32, u8, u16, u16)> {
+        let mut lengths: HashMap<(u32, u8), (u16, Option<u16>)> = HashMap::new();
+        for key in &self.frame_by_file_order {
+            let Some(frame) = self.frames.get(*key) else {
+                continue;
+            };
+            if frame.ftype != FrameType::Can {
+                continue;
+            }
+            let entry = lengths
+                .entry((frame.id, frame.channel))
+                .or_insert((frame.byte_length, None));
+            if entry.1.is_none() && entry.0 != frame.byte_length {
+                entry.1 = Some(frame.byte_length);
+            }
+        }
+
+        let mut result: Vec<(u32, u8, u16, u16)> = lengths
+            .into_iter()
+            .filter_map(|((id, channel), (dlc_a, dlc_b))| Some((id, channel, dlc_a, dlc_b?)))
+            .collect();
+        result.sort_by_key(|(id, channel, _, _)| (*channel, *id));
+        result
+    }
+
+    /// Group the CAN frames by message name resolved from the channel database.
+    /// Frames without a database entry are grouped under `""`. Each group is in file order.
+    pub fn group_frames_by_msg_name(&self) -> HashMap<String, Vec<FrameKey>> {
+        let mut groups: HashMap<String, Vec<FrameKey>> = HashMap::new();
+        for &key in &self.frame_by_file_order {
+            let Some(frame) = self.frames.get(key) else {
+                continue;
+            };
+            if frame.ftype != FrameType::Can {
+                continue;
+            }
+            let name: &str = self
+                .get_database_by_channel(frame.channel)
+                .and_then(|db| db.get_message_by_key(frame.msg_key))
+                .map(|msg| msg.name.as_str())
+                .unwrap_or("");
+            match groups.get_mut(name) {
+                Some(keys) => keys.push(key),
+                None => {
+                    groups.insert(name.to_string(), vec![key]);
+                }
+            }
+        }
+        groups
+    }
+
+    /// Return the frames of the message `name`, in file order.
+    ///
+    /// The grouping is computed once by `group_frames_by_msg_name` and cached until
+    /// the frames change.
+    pub fn get_frames_by_msg_name(&self, name: &str) -> Option<&[FrameKey]> {
+        self.msg_name_cache
+            .get_or_init(|| self.group_frames_by_msg_name())
+            .get(name)
+            .map(|keys| keys.as_slice())
+    }
+
+    /// Iterate, in file order, the CAN frames whose DBC sender node is `node_name`,
+    /// across all channels.
+    ///
+    /// The node range is found by binary search in `frame_by_can_sender_node`, the
+    /// unique `(id, channel)` subset is available as `id_chn_by_can_sender_node`.
+    pub fn iter_frames_by_node<'a>(
+        &'a self,
+        node_name: &str,
+    ) -> impl Iterator<Item = &'a Frame> + use<'a> {
+        let sender_name = |key: &FrameKey| -> Option<&'a str> {
+            let frame: &Frame = self.frames.get(*key)?;
+            self.get_database_by_channel(frame.channel)?
+                .get_node_by_key(frame.tx_node_key)
+                .map(|node| node.name.as_str())
+        };
+        let view: &[FrameKey] = &self.frame_by_can_sender_node;
+        let start: usize =
+            view.partition_point(|key| sender_name(key).is_some_and(|n| n < node_name));
+        let end: usize =
+            view.partition_point(|key| sender_name(key).is_some_and(|n| n <= node_name));
+        view[start..end.max(start)]
+            .iter()
+            .filter_map(|key| self.frames.get(*key))
+    }
+
+    /// Return every `(channel, SignalKey)` pair decoded at least once, in order of
+    /// first appearance in the file.
+    pub(crate) fn observed_signals(&self) -> Vec<(u8, SignalKey)> {
+        let mut seen: HashSet<(u8, SignalKey)> = HashSet::new();
+        let mut result: Vec<(u8, SignalKey)> = Vec::new();
+        for key in &self.frame_by_file_order {
+            if let Some(frame) = self.frames.get(*key) {
+                for &sig_key in &frame.sig_keys {
+                    if seen.insert((frame.channel, sig_key)) {
+                        result.push((frame.channel, sig_key));
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Return the sorted names of the signals defined in the database of `channel`
+    /// that have no decoded value, i.e. that no frame of the trace carried.
+    ///
+    /// Signals with a `factor` of 0.0 are constants by definition and are not
+    /// reported. Empty if the channel has no database.
+    pub fn detect_missing_signals(&self, channel: u8) -> Vec<String> {
+        let Some(db) = self.get_database_by_channel(channel) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = db
+            .messages
+            .iter()
+            .flat_map(|(_, msg)| msg.signals.iter())
+            .filter_map(|sig_key| db.get_sig_by_key(*sig_key))
+            .filter(|signal| signal.values.is_empty() && signal.factor != 0.0)
+            .map(|signal| signal.name.clone())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Return one row per signal decoded at least once, with its message and value
+    /// statistics, sorted by channel, numeric message id (`Frame::id`) and signal name.
+    ///
+    /// The message of a signal is the one of the first frame carrying it.
+    pub fn signal_summary_table(&self) -> Vec<SignalSummaryRow> {
+        let mut seen: HashSet<(u8, SignalKey)> = HashSet::new();
+        // rows with the numeric id of their message
+        let mut rows: Vec<(u32, SignalSummaryRow)> = Vec::new();
+        for key in &self.frame_by_file_order {
+            let Some(frame) = self.frames.get(*key) else {
+                continue;
+            };
+            let Some(db) = self.get_database_by_channel(frame.channel) else {
+                continue;
+            };
+            for &sig_key in &frame.sig_keys {
+                if !seen.insert((frame.channel, sig_key)) {
+                    continue;
+                }
+                let Some(signal) = db.get_sig_by_key(sig_key) else {
+                    continue;
+                };
+                let Some(&(_, last_value)) = signal.values.last() else {
+                    continue;
+                };
+                let (min_value, max_value, sum) = signal.values.iter().fold(
+                    (f64::INFINITY, f64::NEG_INFINITY, 0.0),
+                    |(min, max, sum), (_, v)| (min.min(*v), max.max(*v), sum + v),
+                );
+                let last_text: String = if signal.unit.is_empty() {
+                    last_value.to_string()
+                } else {
+                    format!("{} {}", last_value, signal.unit)
+                };
+                let row: SignalSummaryRow = SignalSummaryRow {
+                    channel: frame.channel,
+                    message_id: frame.id_hex.clone(),
+                    message_name: frame.msg_name_to_string(db),
+                    signal_name: signal.name.clone(),
+                    unit: signal.unit.clone(),
+                    min_value,
+                    max_value,
+                    mean_value: sum / signal.values.len() as f64,
+                    sample_count: signal.values.len(),
+                    last_value,
+                    last_text,
+                };
+                rows.push((frame.id, row));
+            }
+        }
+        rows.sort_by(|(a_id, a), (b_id, b)| {
+            (a.channel, a_id, &a.signal_name).cmp(&(b.channel, b_id, &b.signal_name))
+        });
+        rows.into_iter().map(|(_, row)| row).collect()
+    }
+
+    /// Return the key of the signal named `name` decoded on `channel`.
+    ///
+    /// Looked up in the messages of the channel database, without scanning the
+    /// frames; signals without decoded values are skipped.
+    pub fn signal_key_by_name(&self, name: &str, channel: u8) -> Option<SignalKey> {
+        let db: &DatabaseDBC = self.get_database_by_channel(channel)?;
+        db.messages
+            .iter()
+            .flat_map(|(_, msg)| msg.signals.iter().copied())
+            .find(|sig_key| {
+                db.get_sig_by_key(*sig_key)
+                    .is_some_and(|signal| signal.name == name && !signal.values.is_empty())
+            })
+    }
+
+    /// Downsample the signal named `name` on `channel` to at most `max_points` points
+    /// with `utils::lttb::lttb`. Signals within the limit are returned unchanged and
+    /// unknown signals give an empty `Vec`.
+    pub fn downsample_signal(&self, name: &str, channel: u8, max_points: usize) -> Vec<(f64, f64)> {
+        self.signal_key_by_name(name, channel)
+            .and_then(|sig_key| {
+                self.get_database_by_channel(channel)?
+                    .get_sig_by_key(sig_key)
+            })
+            .map(|signal| lttb::lttb(&signal.values, max_points))
+            .unwrap_or_default()
+    }
+
+    /// Histogram of the values of the signal named `sig_name` on `channel`, as
+    /// `(bin_low, bin_high, count)` with `bins` equal-width bins covering
+    /// `[min, max]`. The last bin includes `max`. A constant signal gives a single bin.
+    /// Returns `None` for unknown signals or signals without samples.
+    ///
+    /// # Panics
+    /// Panics if `bins` is 0.
+    pub fn signal_value_histogram(
+        &self,
+        sig_name: &str,
+        channel: u8,
+        bins: usize,
+    ) -> Option<Vec<(f64, f64, usize)>> {
+        assert!(
+            bins > 0,
+            "signal_value_histogram: bins must be greater than 0"
+        );
+        let values: &[(f64, f64)] = self.signal_values_by_name(sig_name, channel)?;
+        if values.is_empty() {
+            return None;
+        }
+        let (min, max) = values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, v)| {
+                (min.min(*v), max.max(*v))
+            });
+        if max - min <= 0.0 {
+            return Some(vec![(min, max, values.len())]);
+        }
+
+        let width: f64 = (max - min) / bins as f64;
+        let mut counts: Vec<usize> = vec![0; bins];
+        for (_, value) in values {
+            let bin: usize = (((value - min) / width) as usize).min(bins - 1);
+            counts[bin] += 1;
+        }
+        Some(
+            counts
+                .into_iter()
+                .enumerate()
+                .map(|(i, count)| {
+                    let low: f64 = min + i as f64 * width;
+                    let high: f64 = if i + 1 == bins { max } else { low + width };
+                    (low, high, count)
+                })
+                .collect(),
+        )
+    }
+
+    /// Decoded samples of the signal named `name` on `channel`.
+    fn signal_values_by_name(&self, name: &str, channel: u8) -> Option<&[(f64, f64)]> {
+        let sig_key: SignalKey = self.signal_key_by_name(name, channel)?;
+        self.get_database_by_channel(channel)?
+            .get_sig_by_key(sig_key)
+            .map(|signal| signal.values.as_slice())
+    }
+
+    /// Return the time series of the signal named `name` on `channel` as x/y arrays.
+    pub fn plot_data_for_signal(&self, name: &str, channel: u8) -> Option<PlotData> {
+        let sig_key: SignalKey = self.signal_key_by_name(name, channel)?;
+        self.plot_data(channel, sig_key)
+    }
+
+    /// Return the plot data of every signal decoded on `channel`, in order of first appearance.
+    pub fn plot_data_for_all_signals(&self, channel: u8) -> Vec<PlotData> {
+        self.observed_signals()
+            .into_iter()
+            .filter(|(ch, _)| *ch == channel)
+            .filter_map(|(ch, sig_key)| self.plot_data(ch, sig_key))
+            .collect()
+    }
+
+    fn plot_data(&self, channel: u8, sig_key: SignalKey) -> Option<PlotData> {
+        let signal = self
+            .get_database_by_channel(channel)?
+            .get_sig_by_key(sig_key)?;
+        let (x, y): (Vec<f64>, Vec<f64>) = signal.values.iter().copied().unzip();
+        Some(PlotData {
+            x,
+            y,
+            label: signal.name.clone(),
+            unit: signal.unit.clone(),
+        })
+    }
+
+    /// Apply a deadband filter to the signal named `name` on `channel`.
+    ///
+    /// A sample is removed when it differs by less than `epsilon` from the last kept
+    /// sample; the first sample is always kept. `values` and `raws` are filtered in
+    /// place together. Returns the number of removed samples.
+    ///
+    /// # Panics
+    /// If `epsilon` is negative.
+    pub fn remove_signal_noise(&mut self, name: &str, channel: u8, epsilon: f64) -> usize {
+        assert!(
+            epsilon >= 0.0,
+            "remove_signal_noise: epsilon must be >= 0.0"
+        );
+        match self.signal_key_by_name(name, channel) {
+            Some(sig_key) => self.apply_deadband(channel, sig_key, epsilon),
+            None => 0,
+        }
+    }
+
+    /// Apply `remove_signal_noise` to every decoded signal of every channel.
+    /// Returns the total number of removed samples.
+    ///
+    /// # Panics
+    /// If `epsilon` is negative.
+    pub fn remove_signal_noise_all(&mut self, epsilon: f64) -> usize {
+        assert!(
+            epsilon >= 0.0,
+            "remove_signal_noise_all: epsilon must be >= 0.0"
+        );
+        self.observed_signals()
+            .into_iter()
+            .map(|(channel, sig_key)| self.apply_deadband(channel, sig_key, epsilon))
+            .sum()
+    }
+
+    fn apply_deadband(&mut self, channel: u8, sig_key: SignalKey, epsilon: f64) -> usize {
+        let Some(signal) = self
+            .get_mut_database_by_channel(channel)
+            .and_then(|db| db.get_sig_by_key_mut(sig_key))
+        else {
+            return 0;
+        };
+
+        let mut keep: Vec<bool> = Vec::with_capacity(signal.values.len());
+        let mut last_kept: Option<f64> = None;
+        for &(_, value) in &signal.values {
+            let kept: bool = match last_kept {
+                Some(last) => (value - last).abs() >= epsilon,
+                None => true,
+            };
+            if kept {
+                last_kept = Some(value);
+            }
+            keep.push(kept);
+        }
+
+        let mut idx: usize = 0;
+        signal.values.retain(|_| {
+            idx += 1;
+            keep[idx - 1]
+        });
+        let mut idx: usize = 0;
+        signal.raws.retain(|_| {
+            idx += 1;
+            keep.get(idx - 1).copied().unwrap_or(true)
+        });
+
+        let removed: usize = keep.iter().filter(|kept| !**kept).count();
+        if removed > 0 {
+            // samples already saved are gone
+            self.reset_checkpoint_cursor();
+        }
+        removed
+    }
+
+    /// Return the decoded time series of a signal, looked up in the channel
+    /// databases starting from the lowest channel number.
+    pub fn get_signal_values(&self, sig_key: SignalKey) -> Option<&[(f64, f64)]> {
+        let mut channels: Vec<u8> = self.channel_map.keys().copied().collect();
+        channels.sort_unstable();
+        channels
+            .into_iter()
+            .find_map(|ch| self.get_database_by_channel(ch)?.get_sig_by_key(sig_key))
+            .map(|signal| signal.values.as_slice())
+    }
+
+    /// Iterate the value changes of a signal as `(timestamp, old_value, new_value)`.
+    ///
+    /// Consecutive samples are compared with `SIGNAL_EPSILON` tolerance, so the first
+    /// sample never produces a transition. The iterator is empty for unknown signals,
+    /// signals with less than 2 samples or constant signals.
+    pub fn iter_signal_transitions(
+        &self,
+        sig_key: SignalKey,
+    ) -> impl Iterator<Item = (f64, f64, f64)> + '_ {
+        self.get_signal_values(sig_key)
+            .unwrap_or(&[])
+            .windows(2)
+            .filter(|pair| (pair[1].1 - pair[0].1).abs() > SIGNAL_EPSILON)
+            .map(|pair| (pair[1].0, pair[0].1, pair[1].1))
+    }
+
+    /// Return `Some(true)` if the samples of a signal never decrease, `Some(false)`
+    /// if they do at least once, `None` for unknown signals or less than 2 samples.
+    pub fn verify_signal_monotonicity(&self, sig_key: SignalKey) -> Option<bool> {
+        is_monotone(self.get_signal_values(sig_key)?)
+    }
+
+    /// Return every decrease of a signal as `(timestamp, previous_value, current_value)`,
+    /// in time order. Empty for monotone or unknown signals.
+    pub fn find_monotonicity_violations(&self, sig_key: SignalKey) -> Vec<(f64, f64, f64)> {
+        monotonicity_violations(self.get_signal_values(sig_key).unwrap_or(&[]))
+    }
+
+    /// Return, in chronological order, the frames carrying a transition of a signal
+    /// from `from` to `to`, both compared with `SIGNAL_EPSILON` tolerance.
+    ///
+    /// The frame of a transition is the frame carrying `sig_key` with the timestamp
+    /// of the `to` sample. Empty if the transition never occurs.
+    pub fn frames_where_signal_changes_state(
+        &self,
+        sig_key: SignalKey,
+        from: f64,
+        to: f64,
+    ) -> Vec<FrameKey> {
+        let values: &[(f64, f64)] = self.get_signal_values(sig_key).unwrap_or(&[]);
+        let timestamps: Vec<f64> = transition_timestamps(values, from, to);
+        if timestamps.is_empty() {
+            return Vec::new();
+        }
+        self.frames_carrying_signal_at(sig_key, timestamps)
+    }
+
+    /// Frames of `frame_by_timestamp` carrying `sig_key` at each of `timestamps`,
+    /// sorted ascending. Timestamps without such a frame are skipped.
+    fn frames_carrying_signal_at(&self, sig_key: SignalKey, timestamps: Vec<f64>) -> Vec<FrameKey> {
+        let carriers: Vec<(f64, FrameKey)> = self
+            .frame_by_timestamp
+            .iter()
+            .filter_map(|key| {
+                let frame: &Frame = self.frames.get(*key)?;
+                frame
+                    .sig_keys
+                    .contains(&sig_key)
+                    .then_some((self.block_adjusted_timestamp(frame), *key))
+            })
+            .collect();
+        timestamps
+            .into_iter()
+            .filter_map(|ts| {
+                let idx: usize = carriers.partition_point(|(t, _)| *t < ts);
+                carriers
+                    .get(idx)
+                    .filter(|(t, _)| *t == ts)
+                    .map(|(_, key)| *key)
+            })
+            .collect()
+    }
+
+    /// Return every value change of every decoded signal, across all channels, in
+    /// timestamp order.
+    ///
+    /// Signals are decoded again from the payload of each frame of
+    /// `frame_by_timestamp` and compared to the previous value of the same signal on
+    /// the same channel with `SIGNAL_EPSILON` tolerance, so the first sample of a
+    /// signal never produces an event.
+    pub fn build_signal_change_event_log(&self) -> Vec<SignalChangeEvent> {
+        let mut previous: HashMap<(u8, SignalKey), f64> = HashMap::new();
+        let mut events: Vec<SignalChangeEvent> = Vec::new();
+        for key in &self.frame_by_timestamp {
+            let Some(frame) = self.frames.get(*key) else {
+                continue;
+            };
+            if frame.sig_keys.is_empty() {
+                continue;
+            }
+            let Some(db) = self.get_database_by_channel(frame.channel) else {
+                continue;
+            };
+            let payload: Vec<u8> = frame.payload_bytes();
+            for &sig_key in &frame.sig_keys {
+                let Some(signal) = db.get_sig_by_key(sig_key) else {
+                    continue;
+                };
+                let value: f64 =
+                    signal.extract_raw_i64(&payload) as f64 * signal.factor + signal.offset;
+                if let Some(old_value) = previous.insert((frame.channel, sig_key), value)
+                    && (value - old_value).abs() > SIGNAL_EPSILON
+                {
+                    events.push(SignalChangeEvent {
+                        timestamp: self.block_adjusted_timestamp(frame),
+                        frame_key: *key,
+                        sig_key,
+                        old_value,
+                        new_value: value,
+                        channel: frame.channel,
+                        signal_name: signal.name.clone(),
+                    });
+                }
+            }
+        }
+        events
+    }
+
+    /// Change the `factor` and `offset` of a signal and recompute its decoded
+    /// `values` from the stored raw values: `value = raw * new_factor + new_offset`.
+    ///
+    /// This modifies the loaded DBC in place, in the first channel database
+    /// (lowest channel number) holding `sig_key`, like `get_signal_values`.
+    pub fn apply_signal_scaling(
+        &mut self,
+        sig_key: SignalKey,
+        new_factor: f64,
+        new_offset: f64,
+    ) -> Result<(), SignalScaleError> {
+        let mut channels: Vec<u8> = self.channel_map.keys().copied().collect();
+        channels.sort_unstable();
+        let channel: u8 = channels
+            .into_iter()
+            .find(|ch| {
+                self.get_database_by_channel(*ch)
+                    .is_some_and(|db| db.get_sig_by_key(sig_key).is_some())
+            })
+            .ok_or(SignalScaleError::SignalNotFound(sig_key))?;
+        let signal = self
+            .get_mut_database_by_channel(channel)
+            .and_then(|db| db.get_sig_by_key_mut(sig_key))
+            .ok_or(SignalScaleError::SignalNotFound(sig_key))?;
+
+        signal.factor = new_factor;
+        signal.offset = new_offset;
+        signal.values = signal
+            .raws
+            .iter()
+            .map(|(timestamp, raw)| (*timestamp, *raw as f64 * new_factor + new_offset))
+            .collect();
+        self.invalidate_caches();
+        // values already saved change
+        self.reset_checkpoint_cursor();
+        Ok(())
+    }
+
+    /// Return the rate of change of a signal per second as `(timestamp, derivative)`,
+    /// one point per pair of consecutive samples dated at the later one.
+    ///
+    /// Pairs with the same timestamp are skipped. Empty for unknown signals or
+    /// less than 2 samples.
+    pub fn compute_signal_derivative(&self, sig_key: SignalKey) -> Vec<(f64, f64)> {
+        self.get_signal_values(sig_key)
+            .unwrap_or(&[])
+            .windows(2)
+            .filter(|pair| pair[1].0 != pair[0].0)
+            .map(|pair| (pair[1].0, (pair[1].1 - pair[0].1) / (pair[1].0 - pair[0].0)))
+            .collect()
+    }
+
+    /// Return the cumulative integral of a signal over time as `(timestamp, integral)`,
+    /// computed with the trapezoidal rule and starting at 0.0 on the first sample.
+    ///
+    /// Empty for unknown signals or less than 2 samples.
+    pub fn compute_signal_integral(&self, sig_key: SignalKey) -> Vec<(f64, f64)> {
+        let values: &[(f64, f64)] = self.get_signal_values(sig_key).unwrap_or(&[]);
+        if values.len() < 2 {
+            return Vec::new();
+        }
+        let mut integral: f64 = 0.0;
+        let mut result: Vec<(f64, f64)> = Vec::with_capacity(values.len());
+        result.push((values[0].0, integral));
+        for pair in values.windows(2) {
+            integral += (pair[1].0 - pair[0].0) * (pair[0].1 + pair[1].1) / 2.0;
+            result.push((pair[1].0, integral));
+        }
+        result
+    }
+
+    /// Compute the single sided magnitude spectrum of a signal.
+    ///
+    /// The signal is first resampled at `sample_rate_hz` with zero-order hold over
+    /// its own time span, then windowed with `window_fn` and transformed with
+    /// `rustfft`. Returns `(frequency_hz, magnitude)` for the positive frequencies,
+    /// magnitudes normalised by the sample count. Returns an empty `Vec` if the signal
+    /// has fewer than 2 samples or `sample_rate_hz <= 0`.
+    #[cfg(feature = "fft")]
+    pub fn compute_signal_fft(
+        &self,
+        sig_key: SignalKey,
+        sample_rate_hz: f64,
+        window_fn: WindowFunction,
+    ) -> Vec<(f64, f64)> {
+        let values: &[(f64, f64)] = self.get_signal_values(sig_key).unwrap_or(&[]);
+        if values.len() < 2 || sample_rate_hz.is_nan() || sample_rate_hz <= 0.0 {
+            return Vec::new();
+        }
+        let start: f64 = values[0].0;
+        let end: f64 = values[values.len() - 1].0;
+        let samples: Vec<f64> = resample::zoh(values, start, end, 1.0 / sample_rate_hz)
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
+        fft::magnitude_spectrum(&samples, sample_rate_hz, window_fn)
+    }
+
+    /// Compute the normalised cross-correlation of two signals as `(lag_s, correlation)`.
+    ///
+    /// Both signals are resampled with zero-order hold on a common grid over the
+    /// time span they share, spaced by the smaller of their average sample
+    /// intervals. Lags go from `-max_lag_s` to `+max_lag_s`; a positive lag means
+    /// `b` follows `a`, so the lag of the maximum is the estimated delay. The
+    /// zero-lag auto-correlation is 1.0. Returns an empty `Vec` if the signals do
+    /// not overlap in time, have fewer than 2 samples or are constant.
+    #[cfg(feature = "fft")]
+    pub fn compute_signal_cross_correlation(
+        &self,
+        a: SignalKey,
+        b: SignalKey,
+        max_lag_s: f64,
+    ) -> Vec<(f64, f64)> {
+        let values_a: &[(f64, f64)] = self.get_signal_values(a).unwrap_or(&[]);
+        let values_b: &[(f64, f64)] = self.get_signal_values(b).unwrap_or(&[]);
+        if values_a.len() < 2 || values_b.len() < 2 || max_lag_s.is_nan() || max_lag_s < 0.0 {
+            return Vec::new();
+        }
+        let average_interval = |values: &[(f64, f64)]| {
+            (values[values.len() - 1].0 - values[0].0) / (values.len() - 1) as f64
+        };
+        let interval_s: f64 = average_interval(values_a).min(average_interval(values_b));
+        let start: f64 = values_a[0].0.max(values_b[0].0);
+        let end: f64 = values_a[values_a.len() - 1]
+            .0
+            .min(values_b[values_b.len() - 1].0);
+
+        let grid = |values: &[(f64, f64)]| -> Vec<f64> {
+            resample::zoh(values, start, end, interval_s)
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect()
+        };
+        let samples_a: Vec<f64> = grid(values_a);
+        let samples_b: Vec<f64> = grid(values_b);
+        if samples_a.len() < 2 {
+            return Vec::new();
+        }
+
+        let max_lag: usize = (max_lag_s / interval_s).round() as usize;
+        fft::cross_correlation(&samples_a, &samples_b, max_lag)
+            .into_iter()
+            .map(|(lag, correlation)| (lag as f64 * interval_s, correlation))
+            .collect()
+    }
+
+    /// Detect the peaks of a signal as `(timestamp, value)` in time order.
+    ///
+    /// A sample is a peak if it is strictly greater than both neighbours and at least
+    /// `min_peak_height`. As in scipy's `find_peaks`, peaks closer than
+    /// `min_distance_s` to a taller kept peak are then suppressed, tallest first.
+    /// Returns an empty `Vec` if the signal has fewer than 3 samples.
+    pub fn signal_peak_detection(
+        &self,
+        sig_key: SignalKey,
+        min_peak_height: f64,
+        min_distance_s: f64,
+    ) -> Vec<(f64, f64)> {
+        let values: &[(f64, f64)] = self.get_signal_values(sig_key).unwrap_or(&[]);
+        find_peaks(values, min_peak_height, min_distance_s)
+    }
+
+    /// Resample every decoded signal on a common time grid with zero-order hold.
+    ///
+    /// The grid spans from the earliest first sample to the latest last sample of
+    /// all signals, with `interval_s` spacing, so every returned `Vec` has the same
+    /// length. Grid points before the first sample of a signal hold `f64::NAN`.
+    /// Keys are `"name@channel"`; when signals of different messages share a name on
+    /// the same channel, the first one decoded in the file is kept. Returns an empty
+    /// map if `interval_s <= 0`.
+    pub fn resample_all_signals(&self, interval_s: f64) -> HashMap<String, Vec<(f64, f64)>> {
+        let mut result: HashMap<String, Vec<(f64, f64)>> = HashMap::new();
+        if interval_s.is_nan() || interval_s <= 0.0 {
+            return result;
+        }
+
+        let mut series: Vec<(String, &[(f64, f64)])> = Vec::new();
+        let mut start: f64 = f64::INFINITY;
+        let mut end: f64 = f64::NEG_INFINITY;
+        for (channel, sig_key) in self.observed_signals() {
+            let Some(signal) = self
+                .get_database_by_channel(channel)
+                .and_then(|db| db.get_sig_by_key(sig_key))
+            else {
+                continue;
+            };
+            let (Some(first), Some(last)) = (signal.values.first(), signal.values.last()) else {
+                continue;
+            };
+            start = start.min(first.0);
+            end = end.max(last.0);
+            series.push((format!("{}@{}", signal.name, channel), &signal.values));
+        }
+
+        for (name, values) in series {
+            result
+                .entry(name)
+                .or_insert_with(|| resample::zoh(values, start, end, interval_s));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+impl Log {
+    /// Log holding `frames` in the given file order, with a CAN channel without
+    /// database for every channel used and all the views built.
+    pub(crate) fn from_frames(frames: Vec<Frame>) -> Log {
+        let mut log: Log = Log::default();
+        for frame in frames {
+            if !log.channel_map.contains_key(&frame.channel) {
+                log.add_can_channel(frame.channel, None);
+            }
+            let key: FrameKey = log.frames.insert(frame);
+            log.frame_by_file_order.push(key);
+        }
+        sort::rebuild_views(&mut log);
+        log
+    }
+}
+
+/// Entry of `node_key` in `nodes`, created on first use. `None` if the node is not in `db`.
+fn topology_node<'a>(
+    nodes: &'a mut HashMap<NodeKey, NodeTopology>,
+    db: &DatabaseDBC,
+    node_key: NodeKey,
+) -> Option<&'a mut NodeTopology> {
+    let name: &str = &db.get_node_by_key(node_key)?.name;
+    Some(nodes.entry(node_key).or_insert_with(|| NodeTopology {
+        node_name: name.to_string(),
+        ..NodeTopology::default()
+    }))
+}
+
+/// Number of differing bits between two payloads, the shorter one padded with `0x00`.
+fn hamming_distance(a: &[u8], b: &[u8]) -> u32 {
+    (0..a.len().max(b.len()))
+        .map(|i| {
+            let x: u8 = a.get(i).copied().unwrap_or(0);
+            let y: u8 = b.get(i).copied().unwrap_or(0);
+            (x ^ y).count_ones()
+        })
+        .sum()
+}
+
+/// Timestamps of the samples of `values` equal to `to` that follow a sample equal
+/// to `from`, both compared with `SIGNAL_EPSILON` tolerance.
+fn transition_timestamps(values: &[(f64, f64)], from: f64, to: f64) -> Vec<f64> {
+    let matches = |value: f64, target: f64| (value - target).abs() <= SIGNAL_EPSILON;
+    values
+        .windows(2)
+        .filter(|pair| matches(pair[0].1, from) && matches(pair[1].1, to))
+        .map(|pair| pair[1].0)
+        .collect()
+}
+
+/// See `Log::verify_signal_monotonicity`.
+fn is_monotone(values: &[(f64, f64)]) -> Option<bool> {
+    if values.len() < 2 {
+        return None;
+    }
+    Some(values.windows(2).all(|pair| pair[1].1 >= pair[0].1))
+}
+
+/// See `Log::find_monotonicity_violations`.
+fn monotonicity_violations(values: &[(f64, f64)]) -> Vec<(f64, f64, f64)> {
+    values
+        .windows(2)
+        .filter(|pair| pair[1].1 < pair[0].1)
+        .map(|pair| (pair[1].0, pair[0].1, pair[1].1))
+        .collect()
+}
+
+/// Peaks of `values`, see `Log::signal_peak_detection`.
+fn find_peaks(values: &[(f64, f64)], min_peak_height: f64, min_distance_s: f64) -> Vec<(f64, f64)> {
+    if values.len() < 3 {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<(f64, f64)> = values
+        .windows(3)
+        .filter(|w| w[1].1 > w[0].1 && w[1].1 > w[2].1 && w[1].1 >= min_peak_height)
+        .map(|w| w[1])
+        .collect();
+
+    // tallest first, so that each kept peak suppresses its smaller neighbours
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.total_cmp(&b.0)));
+    let mut peaks: Vec<(f64, f64)> = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        if peaks
+            .iter()
+            .all(|peak| (peak.0 - candidate.0).abs() >= min_distance_s)
+        {
+            peaks.push(candidate);
+        }
+    }
+    peaks.sort_by(|a, b| a.0.total_cmp(&b.0));
+    peaks
+}
+
+/// Frames per second of `count` frames spanning `[first, last]`.
+fn frame_rate(count: usize, first: f64, last: f64) -> Option<f64> {
+    let duration: f64 = last - first;
+    if count < 2 || duration.is_nan() || duration <= 0.0 {
+        return None;
+    }
+    Some(count as f64 / duration)
 }
 
+/// Selects one of the sorted `FrameKey` views of a `Log`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FrameView {
+    // --- All Frames Order by generic parameters  ---
+    #[default]
+    FileOrder,
+    Timestamp,
+    BlockTimestamp,
+    Channel,
+    Direction,
+
+    // ---  All Frames Order by CAN parameters   ---
+    CanMsgName,
+    CanSignalName,
+    CanMsgId,
+    CanDlc,
+    CanProtocol,
+    Protocol,
+    CanPriority,
+    CanCyclicOnly,
+    CanEventDrivenOnly,
+    CanSenderNode,
+    CanData,
+    CanComment,
+
+    // --- ID-Channel Order by generic parameters  ---
+    IdChnTimestamp,
+    IdChnChannel,
+    IdChnDirection,
+
+    // --- ID-Channel Order by CAN parameters  ---
+    IdChnCanMsgName,
+    IdChnCanMsgId,
+    IdChnCanDlc,
+    IdChnCanProtocol,
+    IdChnCanSenderNode,
+    IdChnCanData,
+    IdChnCanComment,
+}
+
+/// Tolerance used when comparing decoded signal values.
+pub const SIGNAL_EPSILON: f64 = 1e-9;
+
+/// Nominal CAN bit rate assumed when `ChannelInfo::bit_rate` is not set.
+pub const DEFAULT_BIT_RATE: u32 = 500_000;
+
+/// Bits an error frame occupies on the bus: the superposed error flags (up to 12
+/// dominant bits) and the 8-bit error delimiter.
+pub const ERROR_FRAME_BIT_COUNT: u32 = 20;
+
+/// Number of frames an (id, channel) pair must exceed to be considered cyclic.
+pub const CYCLIC_MIN_REPEAT_COUNT: usize = 3;
+
+/// Coefficient of variation (std / mean) of the inter-frame gaps below which an
+/// (id, channel) pair is considered cyclic.
+pub const CYCLIC_MAX_GAP_CV: f64 = 0.5;
+
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChannelInfo {
     pub number: u8,
     pub tipo: ChannelType,
     pub database: Option<DatabaseDBC>,
+    /// Nominal bus bit rate in bit/s, `None` falls back to `DEFAULT_BIT_RATE`.
+    pub bit_rate: Option<u32>,
+    /// Human-readable bus name, e.g. `"Powertrain"`. Empty if unknown.
+    pub network_name: String,
+    /// Network namespace or interface the channel was recorded on, e.g. `"vcan0"`.
+    pub namespace: Option<String>,
+    /// Frame lines of the channel counted by the pre-scan of
+    /// `ParseOptions::prescan_for_capacity`, 0 when no pre-scan was done.
+    pub estimated_frame_count: usize,
 }
 impl ChannelInfo {
     pub fn clear(&mut self) {
         *self = ChannelInfo::default();
     }
+
+    /// Return the configured bit rate or `DEFAULT_BIT_RATE` when unset.
+    pub fn bit_rate_or_default(&self) -> u32 {
+        self.bit_rate.unwrap_or(DEFAULT_BIT_RATE)
+    }
+
+    /// Return `network_name`, or `"Channel {number}"` when it is empty.
+    pub fn display_name(&self) -> String {
+        if self.network_name.is_empty() {
+            format!("Channel {}", self.number)
+        } else {
+            self.network_name.clone()
+        }
+    }
+
     pub fn db_name_to_string(&self) -> String {
         if let Some(db) = &self.database {
             db.name.clone()
@@ -130,6 +2514,7 @@ impl ChannelInfo {
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChannelType {
     #[default]
     Can,
@@ -144,3 +2529,207 @@ impl std::fmt::Display for ChannelType {
         f.write_str(label)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::frame::FrameBuilder;
+
+    fn can_frame(timestamp: f64, channel: u8, id: u32) -> Frame {
+        FrameBuilder::new()
+            .timestamp(timestamp)
+            .channel(channel)
+            .id(id)
+            .data("00 00 00 00 00 00 00 00")
+            .build()
+    }
+
+    #[test]
+    fn cycle_time_violation_flags_late_frame() {
+        let log: Log = Log::from_frames(
+            [0.0, 0.010, 0.020, 0.032, 0.042]
+                .into_iter()
+                .map(|ts| can_frame(ts, 1, 0x100))
+                .collect(),
+        );
+        let violations: Vec<FrameKey> = log.frame_cycle_time_violations(0x100, 1, 10.0, 10.0);
+        assert_eq!(violations, vec![log.frame_by_file_order[3]]);
+    }
+
+    #[test]
+    fn busload_of_single_frame_in_bin() {
+        // 8-byte standard frame: 111 bits, i.e. 125 us at 888 kbit/s
+        let mut log: Log = Log::from_frames(vec![can_frame(0.0005, 1, 0x100)]);
+        log.channel_map.get_mut(&1).unwrap().bit_rate = Some(888_000);
+        let load: Vec<(f64, f64)> = log.compute_busload_per_interval(1, 1);
+        assert_eq!(load.len(), 1);
+        assert!((load[0].0 - 0.0005).abs() < 1e-12);
+        assert!((load[0].1 - 0.125).abs() < 1e-9);
+    }
+
+    #[test]
+    fn busload_counts_remote_and_error_frames() {
+        let remote: Frame = FrameBuilder::new()
+            .timestamp(0.0005)
+            .channel(1)
+            .id(0x100)
+            .ftype(FrameType::Remote)
+            .build();
+        let error: Frame = FrameBuilder::new()
+            .timestamp(0.0015)
+            .channel(1)
+            .ftype(FrameType::ErrorFrame)
+            .build();
+        let mut log: Log = Log::from_frames(vec![remote, error]);
+        log.channel_map.get_mut(&1).unwrap().bit_rate = Some(1_000_000);
+        let load: Vec<(f64, f64)> = log.compute_busload_per_interval(1, 1);
+        assert_eq!(load.len(), 2);
+        assert!((load[0].1 - 0.047).abs() < 1e-9);
+        assert!((load[1].1 - 0.020).abs() < 1e-9);
+    }
+
+    #[test]
+    fn frequency_cache_rebuilt_after_invalidation() {
+        let mut log: Log =
+            Log::from_frames(vec![can_frame(0.1, 1, 0x100), can_frame(0.2, 1, 0x100)]);
+        assert_eq!(
+            log.get_or_build_frequency_cache().get(&(0x100, 1)),
+            Some(&2)
+        );
+
+        let key: FrameKey = log.frames.insert(can_frame(0.3, 1, 0x100));
+        log.frame_by_file_order.push(key);
+        sort::rebuild_views(&mut log);
+        assert_eq!(
+            log.get_or_build_frequency_cache().get(&(0x100, 1)),
+            Some(&3)
+        );
+
+        // direct changes of `frames` need an explicit invalidation
+        let key: FrameKey = log.frames.insert(can_frame(0.4, 1, 0x100));
+        log.frame_by_file_order.push(key);
+        log.frame_by_timestamp.push(key);
+        log.invalidate_frequency_cache();
+        assert_eq!(
+            log.get_or_build_frequency_cache().get(&(0x100, 1)),
+            Some(&4)
+        );
+    }
+
+    #[test]
+    fn babbling_idiot_flagged_above_threshold() {
+        let mut frames: Vec<Frame> = (0..1000)
+            .map(|i| can_frame(f64::from(i) * 0.001, 1, 0x100))
+            .collect();
+        frames.extend((0..10).map(|i| can_frame(f64::from(i) * 0.1, 1, 0x200)));
+        let log: Log = Log::from_frames(frames);
+
+        let flagged: Vec<(u32, u8, f64)> = log.detect_babbling_idiot(100.0);
+        assert_eq!(flagged.len(), 1);
+        assert_eq!((flagged[0].0, flagged[0].1), (0x100, 1));
+        assert!((flagged[0].2 - 1000.0).abs() < 2.0);
+        assert!(log.detect_babbling_idiot(2000.0).is_empty());
+    }
+
+    #[test]
+    fn first_and_last_frame_by_id_and_channel() {
+        let log: Log = Log::from_frames(vec![
+            can_frame(0.5, 1, 0x200),
+            can_frame(0.1, 2, 0x100),
+            can_frame(0.2, 1, 0x100),
+            can_frame(0.9, 1, 0x100),
+            can_frame(0.7, 2, 0x100),
+            can_frame(0.3, 1, 0x200),
+        ]);
+        assert_eq!(log.time_of_first_frame_by_id(0x100, 1), Some(0.2));
+        assert_eq!(log.time_of_last_frame_by_id(0x100, 1), Some(0.9));
+        assert_eq!(log.time_of_first_frame_by_id(0x100, 2), Some(0.1));
+        assert_eq!(log.time_of_last_frame_by_id(0x100, 2), Some(0.7));
+        assert_eq!(log.time_of_first_frame_by_id(0x200, 1), Some(0.3));
+        assert_eq!(log.time_of_last_frame_by_id(0x200, 1), Some(0.5));
+        assert_eq!(log.time_of_first_frame_by_id(0x200, 2), None);
+        assert_eq!(log.time_of_last_frame_by_id(0x300, 1), None);
+    }
+
+    #[test]
+    fn monotonicity_of_empty_signal() {
+        let log: Log = Log::default();
+        assert_eq!(log.verify_signal_monotonicity(SignalKey::default()), None);
+        assert!(
+            log.find_monotonicity_violations(SignalKey::default())
+                .is_empty()
+        );
+        assert_eq!(is_monotone(&[]), None);
+        assert_eq!(is_monotone(&[(0.0, 1.0)]), None);
+        assert!(monotonicity_violations(&[]).is_empty());
+    }
+
+    #[test]
+    fn monotonicity_of_monotone_signal() {
+        let values: [(f64, f64); 4] = [(0.0, 1.0), (0.1, 2.0), (0.2, 2.0), (0.3, 5.0)];
+        assert_eq!(is_monotone(&values), Some(true));
+        assert!(monotonicity_violations(&values).is_empty());
+    }
+
+    #[test]
+    fn monotonicity_of_violated_signal() {
+        let values: [(f64, f64); 5] = [(0.0, 1.0), (0.1, 3.0), (0.2, 2.0), (0.3, 4.0), (0.4, 0.0)];
+        assert_eq!(is_monotone(&values), Some(false));
+        assert_eq!(
+            monotonicity_violations(&values),
+            vec![(0.2, 3.0, 2.0), (0.4, 4.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn state_transition_occurring_twice() {
+        // 4-state machine cycling 0 -> 1 -> 2 -> 3 twice
+        let states: [f64; 8] = [0.0, 1.0, 2.0, 3.0, 0.0, 1.0, 2.0, 3.0];
+        let values: Vec<(f64, f64)> = states
+            .iter()
+            .enumerate()
+            .map(|(i, state)| (i as f64 * 0.1, *state))
+            .collect();
+        let timestamps: Vec<f64> = transition_timestamps(&values, 1.0, 2.0);
+        assert_eq!(timestamps, vec![values[2].0, values[6].0]);
+        assert!(transition_timestamps(&values, 2.0, 1.0).is_empty());
+
+        let sig_key: SignalKey = SignalKey::default();
+        let frames: Vec<Frame> = values
+            .iter()
+            .map(|(ts, _)| {
+                let mut frame: Frame = can_frame(*ts, 1, 0x100);
+                frame.sig_keys.push(sig_key);
+                frame
+            })
+            .collect();
+        let log: Log = Log::from_frames(frames);
+        assert_eq!(
+            log.frames_carrying_signal_at(sig_key, timestamps),
+            vec![log.frame_by_file_order[2], log.frame_by_file_order[6]]
+        );
+    }
+
+    #[test]
+    fn peak_detection_finds_three_peaks() {
+        let values: Vec<(f64, f64)> = vec![
+            (0.0, 0.0),
+            (1.0, 5.0),
+            (2.0, 0.0),
+            (2.5, 1.0),
+            (3.0, 0.0),
+            (4.0, 3.0),
+            (4.2, 2.0),
+            (4.4, 2.5),
+            (5.0, 0.0),
+            (6.0, 7.0),
+            (7.0, 0.0),
+        ];
+        // 2.5 is below the height, 4.4 within the distance of the taller 4.0
+        assert_eq!(
+            find_peaks(&values, 2.0, 0.5),
+            vec![(1.0, 5.0), (4.0, 3.0), (6.0, 7.0)]
+        );
+        assert!(find_peaks(&values[..2], 0.0, 0.0).is_empty());
+    }
+}
@@ -1,5 +1,13 @@
 pub mod absolute_time;
 pub mod errors;
 pub mod frame;
+#[cfg(any(debug_assertions, feature = "integrity"))]
+pub mod integrity;
 pub mod keys;
 pub mod log;
+pub mod parse_options;
+pub mod plot_data;
+pub mod signal_event;
+pub mod statistics;
+pub mod topology;
+pub mod trigger;
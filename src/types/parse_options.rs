@@ -0,0 +1,82 @@
+/// Options controlling how strict `parse::from_asc_file_with_options` is.
+///
+/// `ParseOptions::default()` is the strict configuration used by
+/// `parse::from_asc_file`, `ParseOptions::lenient()` accepts everything that can
+/// still produce a meaningful `Log`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    /// Accept a 0 byte file and return an empty `Log` instead of `AscParseError::FileEmpty`.
+    pub allow_empty_file: bool,
+    /// Count the keywords of the lines the parser does not support in `Log::skipped_tokens`.
+    pub collect_skipped_tokens: bool,
+    /// Unit and total used to report progress, see `parse::from_asc_file_with_progress`.
+    pub progress_mode: ProgressMode,
+    /// Known number of lines of the file. When set, progress is reported in lines
+    /// against this total whatever the `progress_mode`.
+    pub estimate_total_lines: Option<usize>,
+    /// Accept files whose `version` header has a major version above
+    /// `parse::SUPPORTED_ASC_MAJOR_VERSION` instead of `AscParseError::UnsupportedVersion`.
+    pub ignore_version: bool,
+    /// Only keep the frames of these channels, `None` keeps every channel.
+    /// `Log::channel_map` is not affected.
+    pub channel_filter: Option<Vec<u8>>,
+    /// Stop at the first line that cannot be read (e.g. invalid UTF-8 of a file
+    /// truncated mid-write) and keep the frames parsed so far, recording the line
+    /// in `Log::parse_warnings` instead of returning `AscParseError::Read`.
+    pub allow_truncated: bool,
+    /// Count the frame lines of each channel in a first pass over the file and
+    /// reserve the frame storage up front. The pre-scan doubles the I/O but avoids
+    /// the reallocations of large files; the counts are stored in
+    /// `ChannelInfo::estimated_frame_count`. Lines dropped by `channel_filter` or
+    /// `skip_disturbance_blocks` are not counted, and the pass also counts the
+    /// lines for `ProgressMode::LinesTwoPass`.
+    pub prescan_for_capacity: bool,
+    /// Drop the frames between `DISTURBANCE begin` and `DISTURBANCE end`, which
+    /// were injected by the test setup and are not real bus traffic. Default `true`.
+    pub skip_disturbance_blocks: bool,
+    /// Keep the frames of disturbance blocks with `Frame::is_disturbed` set,
+    /// whatever `skip_disturbance_blocks`.
+    pub tag_disturbed_frames: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            allow_empty_file: false,
+            collect_skipped_tokens: false,
+            progress_mode: ProgressMode::default(),
+            estimate_total_lines: None,
+            ignore_version: false,
+            channel_filter: None,
+            allow_truncated: false,
+            prescan_for_capacity: false,
+            skip_disturbance_blocks: true,
+            tag_disturbed_frames: false,
+        }
+    }
+}
+
+/// How `parse::from_asc_file_with_progress` measures progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// `(bytes_read, file_size)`.
+    #[default]
+    Bytes,
+    /// `(lines_parsed, estimated_lines)`, the total is inferred from the average
+    /// line length of the lines read so far.
+    LinesEstimated,
+    /// `(lines_parsed, total_lines)`, the total is counted by a first pass over the file.
+    LinesTwoPass,
+}
+
+impl ParseOptions {
+    /// Options accepting every recoverable anomaly of the input file.
+    pub fn lenient() -> ParseOptions {
+        ParseOptions {
+            allow_empty_file: true,
+            ignore_version: true,
+            allow_truncated: true,
+            ..ParseOptions::default()
+        }
+    }
+}
@@ -0,0 +1,12 @@
+/// Signal time series split in separate x/y arrays, as expected by plotting libraries.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlotData {
+    /// Timestamps in seconds since trace start.
+    pub x: Vec<f64>,
+    /// Physical values.
+    pub y: Vec<f64>,
+    /// Signal name.
+    pub label: String,
+    /// Signal unit as written in the database.
+    pub unit: String,
+}
@@ -0,0 +1,16 @@
+use dbc_editor::types::database::SignalKey;
+
+use crate::types::keys::FrameKey;
+
+/// One value change of a decoded signal, see `Log::build_signal_change_event_log`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SignalChangeEvent {
+    pub timestamp: f64,
+    /// Frame carrying the new value.
+    pub frame_key: FrameKey,
+    pub sig_key: SignalKey,
+    pub old_value: f64,
+    pub new_value: f64,
+    pub channel: u8,
+    pub signal_name: String,
+}
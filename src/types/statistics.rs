@@ -0,0 +1,60 @@
+/// One `CANFD_BUSSTATISTICS` line of a `.asc` file.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
+pub struct FdBusStatEntry {
+    /// Timestamp of the line, or of the previous timestamped line if it has none.
+    pub timestamp: f64,
+    pub channel: u8,
+    /// Frames per second reported by the logger.
+    pub frame_rate: f64,
+    /// Bus load in percent reported by the logger.
+    pub bus_load: f64,
+}
+
+/// One `ERRORCOUNT` line of a `.asc` file.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorCountEntry {
+    /// Timestamp of the line, or of the previous timestamped line if it has none.
+    pub timestamp: f64,
+    pub channel: u8,
+    /// Error type token as written by the logger, e.g. `"Stuff"` or `"CRC"`.
+    pub error_type: String,
+    /// Error count, 0 when the line does not report one.
+    pub count: u32,
+}
+
+/// Per `(id, channel)` summary computed by `Log::compute_message_statistics`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MessageStats {
+    /// Id as written in the trace, e.g. `"17334410x"`.
+    pub id: String,
+    pub channel: u8,
+    /// Message name from the channel database, empty if unknown.
+    pub name: String,
+    pub frame_count: usize,
+    pub first_ts: f64,
+    pub last_ts: f64,
+    /// Cycle time statistics in milliseconds, `NaN` for messages with a single frame.
+    pub mean_cycle_ms: f64,
+    pub min_cycle_ms: f64,
+    pub max_cycle_ms: f64,
+}
+
+/// Per `(channel, signal)` summary computed by `Log::signal_summary_table`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SignalSummaryRow {
+    pub channel: u8,
+    /// Id of the message carrying the signal, as written in the trace.
+    pub message_id: String,
+    pub message_name: String,
+    pub signal_name: String,
+    pub unit: String,
+    pub min_value: f64,
+    pub max_value: f64,
+    pub mean_value: f64,
+    pub sample_count: usize,
+    pub last_value: f64,
+    /// `last_value` followed by the unit, e.g. `"12.5 km/h"`.
+    pub last_text: String,
+}
@@ -0,0 +1,11 @@
+use dbc_editor::types::database::MessageKey;
+
+/// Messages a database node sends and receives, see `Log::build_topology_map`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeTopology {
+    pub node_name: String,
+    /// Messages the node is a sender of.
+    pub tx_messages: Vec<MessageKey>,
+    /// Messages with at least one signal the node receives.
+    pub rx_messages: Vec<MessageKey>,
+}
@@ -0,0 +1,41 @@
+use crate::types::absolute_time::AbsoluteTime;
+use crate::types::frame::Frame;
+use crate::types::keys::FrameKey;
+
+/// A `TRIGGER "name" at <timestamp>` event marker of a `.asc` file.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trigger {
+    /// Timestamp of the event, in seconds from the start of the measurement. Inside
+    /// a trigger block the block start is added, as in `Log::block_adjusted_timestamp`.
+    pub timestamp: f64,
+    pub name: String,
+}
+
+/// A `Begin Triggerblock` ... `End Triggerblock` section of a `.asc` file.
+///
+/// The timestamps of the frames of a block are relative to the block start, as
+/// written in the file. Time ordered views, signal samples and triggers use the
+/// block start plus the frame timestamp instead, see `Log::block_adjusted_timestamp`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(serde::Serialize, serde::Deserialize))]
+pub struct TriggerBlock {
+    /// Start of the block in seconds from `Log::absolute_time`, 0.0 when either
+    /// date is unknown.
+    pub start_timestamp: f64,
+    /// Date of the `Begin Triggerblock` line or of a `date` line inside the block,
+    /// `Log::absolute_time` otherwise.
+    pub absolute_time: AbsoluteTime,
+    /// Frames of the block, in file order.
+    pub frame_keys: Vec<FrameKey>,
+}
+
+/// Start of the trigger block of `frame` plus its timestamp, the frame timestamp
+/// alone when it is outside any block of `trigger_blocks`.
+pub(crate) fn block_adjusted_timestamp(trigger_blocks: &[TriggerBlock], frame: &Frame) -> f64 {
+    let block_start: f64 = frame
+        .trigger_block
+        .and_then(|index| trigger_blocks.get(index))
+        .map_or(0.0, |block| block.start_timestamp);
+    block_start + frame.timestamp
+}
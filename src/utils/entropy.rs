@@ -0,0 +1,42 @@
+/// Shannon entropy of a byte sequence in bits per byte (log base 2).
+///
+/// Returns 0.0 for an empty slice or a slice of identical bytes and 8.0 for a
+/// slice where every byte value occurs equally often.
+pub fn shannon_entropy(bytes: &[u8]) -> f64 {
+    if bytes.is_empty() {
+        return 0.0;
+    }
+    let mut histogram: [usize; 256] = [0; 256];
+    for &byte in bytes {
+        histogram[byte as usize] += 1;
+    }
+
+    let total: f64 = bytes.len() as f64;
+    histogram
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p: f64 = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum::<f64>()
+        .max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entropy_of_identical_bytes_is_zero() {
+        assert_eq!(shannon_entropy(&[]), 0.0);
+        assert_eq!(shannon_entropy(&[0xAA; 64]), 0.0);
+    }
+
+    #[test]
+    fn entropy_of_uniform_bytes_is_eight() {
+        let bytes: Vec<u8> = (0..=255).chain(0..=255).collect();
+        assert!((shannon_entropy(&bytes) - 8.0).abs() < 1e-12);
+        assert!((shannon_entropy(&[0x00, 0xFF]) - 1.0).abs() < 1e-12);
+    }
+}
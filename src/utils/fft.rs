@@ -0,0 +1,123 @@
+use std::f64::consts::PI;
+
+use rustfft::FftPlanner;
+use rustfft::num_complex::Complex;
+
+/// Window applied to the samples before the FFT.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WindowFunction {
+    #[default]
+    Rectangular,
+    Hanning,
+    Hamming,
+}
+
+impl WindowFunction {
+    /// Weight of sample `i` out of `len`.
+    pub fn weight(&self, i: usize, len: usize) -> f64 {
+        if len < 2 {
+            return 1.0;
+        }
+        let phase: f64 = 2.0 * PI * i as f64 / (len - 1) as f64;
+        match self {
+            WindowFunction::Rectangular => 1.0,
+            WindowFunction::Hanning => 0.5 - 0.5 * phase.cos(),
+            WindowFunction::Hamming => 0.54 - 0.46 * phase.cos(),
+        }
+    }
+}
+
+/// Compute the single sided magnitude spectrum of evenly spaced `samples`.
+///
+/// Returns `(frequency_hz, magnitude)` for the bins `0..=len / 2`, magnitudes are
+/// divided by the sample count.
+pub fn magnitude_spectrum(
+    samples: &[f64],
+    sample_rate_hz: f64,
+    window_fn: WindowFunction,
+) -> Vec<(f64, f64)> {
+    let len: usize = samples.len();
+    if len == 0 {
+        return Vec::new();
+    }
+
+    let mut buffer: Vec<Complex<f64>> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, v)| Complex::new(v * window_fn.weight(i, len), 0.0))
+        .collect();
+    FftPlanner::<f64>::new()
+        .plan_fft_forward(len)
+        .process(&mut buffer);
+
+    buffer
+        .iter()
+        .take(len / 2 + 1)
+        .enumerate()
+        .map(|(k, c)| {
+            (
+                k as f64 * sample_rate_hz / len as f64,
+                c.norm() / len as f64,
+            )
+        })
+        .collect()
+}
+
+/// Normalised cross-correlation of two evenly spaced series of the same length.
+///
+/// Returns `(lag, correlation)` for lags `-max_lag..=max_lag` samples, where a
+/// positive lag means `b` is delayed with respect to `a`. Both series are
+/// mean-removed and the result is divided by `sqrt(sum(a^2) * sum(b^2))`, so the
+/// zero-lag auto-correlation is 1.0. Computed in O(N log N) with a zero padded FFT.
+/// Returns an empty `Vec` for empty or constant series.
+pub fn cross_correlation(a: &[f64], b: &[f64], max_lag: usize) -> Vec<(isize, f64)> {
+    let len: usize = a.len().min(b.len());
+    if len == 0 {
+        return Vec::new();
+    }
+    let max_lag: usize = max_lag.min(len - 1);
+
+    let centered = |series: &[f64]| -> Vec<f64> {
+        let mean: f64 = series[..len].iter().sum::<f64>() / len as f64;
+        series[..len].iter().map(|v| v - mean).collect()
+    };
+    let a: Vec<f64> = centered(a);
+    let b: Vec<f64> = centered(b);
+    let norm: f64 =
+        (a.iter().map(|v| v * v).sum::<f64>() * b.iter().map(|v| v * v).sum::<f64>()).sqrt();
+    if norm <= 0.0 {
+        return Vec::new();
+    }
+
+    // zero padding to at least 2 * len - 1 turns the circular correlation into a linear one
+    let fft_len: usize = (2 * len - 1).next_power_of_two();
+    let to_buffer = |series: &[f64]| -> Vec<Complex<f64>> {
+        let mut buffer: Vec<Complex<f64>> = series.iter().map(|v| Complex::new(*v, 0.0)).collect();
+        buffer.resize(fft_len, Complex::new(0.0, 0.0));
+        buffer
+    };
+    let mut planner: FftPlanner<f64> = FftPlanner::new();
+    let forward = planner.plan_fft_forward(fft_len);
+    let inverse = planner.plan_fft_inverse(fft_len);
+
+    let mut spectrum_a: Vec<Complex<f64>> = to_buffer(&a);
+    let mut spectrum_b: Vec<Complex<f64>> = to_buffer(&b);
+    forward.process(&mut spectrum_a);
+    forward.process(&mut spectrum_b);
+    // r[k] = sum_t a[t] * b[t + k]
+    let mut correlation: Vec<Complex<f64>> = spectrum_a
+        .iter()
+        .zip(&spectrum_b)
+        .map(|(x, y)| x.conj() * y)
+        .collect();
+    inverse.process(&mut correlation);
+
+    // rustfft does not normalise the inverse transform
+    let scale: f64 = fft_len as f64 * norm;
+    (-(max_lag as isize)..=max_lag as isize)
+        .map(|lag| {
+            let index: usize = lag.rem_euclid(fft_len as isize) as usize;
+            (lag, correlation[index].re / scale)
+        })
+        .collect()
+}
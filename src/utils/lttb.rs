@@ -0,0 +1,53 @@
+/// Downsample a time-series to at most `threshold` points with the
+/// Largest Triangle Three Buckets algorithm (Steinarsson, 2013).
+///
+/// The first and last points are kept whenever `threshold >= 2`. Inputs already
+/// within the threshold return a copy of `data`.
+pub fn lttb(data: &[(f64, f64)], threshold: usize) -> Vec<(f64, f64)> {
+    if threshold >= data.len() {
+        return data.to_vec();
+    }
+    match threshold {
+        0 => return Vec::new(),
+        1 => return vec![data[0]],
+        2 => return vec![data[0], data[data.len() - 1]],
+        _ => {}
+    }
+
+    let mut sampled: Vec<(f64, f64)> = Vec::with_capacity(threshold);
+    let bucket_size: f64 = (data.len() - 2) as f64 / (threshold - 2) as f64;
+
+    let mut a: usize = 0;
+    sampled.push(data[a]);
+
+    for i in 0..threshold - 2 {
+        // average point of the next bucket
+        let avg_start: usize = ((i + 1) as f64 * bucket_size) as usize + 1;
+        let avg_end: usize = (((i + 2) as f64 * bucket_size) as usize + 1).min(data.len());
+        let avg_len: f64 = (avg_end - avg_start) as f64;
+        let (sum_x, sum_y) = data[avg_start..avg_end]
+            .iter()
+            .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+        let (avg_x, avg_y) = (sum_x / avg_len, sum_y / avg_len);
+
+        // point of the current bucket forming the largest triangle
+        let range_start: usize = (i as f64 * bucket_size) as usize + 1;
+        let range_end: usize = ((i + 1) as f64 * bucket_size) as usize + 1;
+        let (ax, ay) = data[a];
+        let mut max_area: f64 = -1.0;
+        let mut next_a: usize = range_start;
+        for (j, &(x, y)) in data.iter().enumerate().take(range_end).skip(range_start) {
+            let area: f64 = ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+            if area > max_area {
+                max_area = area;
+                next_a = j;
+            }
+        }
+
+        sampled.push(data[next_a]);
+        a = next_a;
+    }
+
+    sampled.push(data[data.len() - 1]);
+    sampled
+}
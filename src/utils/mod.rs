@@ -0,0 +1,5 @@
+pub mod entropy;
+#[cfg(feature = "fft")]
+pub mod fft;
+pub mod lttb;
+pub mod resample;
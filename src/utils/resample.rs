@@ -0,0 +1,35 @@
+/// Number of points of the grid `start + i * interval_s` that fit in `[start, end]`.
+/// Returns 0 for an empty range or a non-positive interval.
+pub fn grid_len(start: f64, end: f64, interval_s: f64) -> usize {
+    if interval_s.is_nan()
+        || interval_s <= 0.0
+        || !start.is_finite()
+        || !end.is_finite()
+        || end < start
+    {
+        return 0;
+    }
+    ((end - start) / interval_s).floor() as usize + 1
+}
+
+/// Resamples a time-series on the grid `start + i * interval_s` up to `end`
+/// using zero-order hold (the last sample at or before each grid point).
+///
+/// `values` must be sorted by timestamp. Grid points that come before the
+/// first sample are set to `f64::NAN`.
+pub fn zoh(values: &[(f64, f64)], start: f64, end: f64, interval_s: f64) -> Vec<(f64, f64)> {
+    let len: usize = grid_len(start, end, interval_s);
+    let mut out: Vec<(f64, f64)> = Vec::with_capacity(len);
+
+    let mut next: usize = 0;
+    let mut current: f64 = f64::NAN;
+    for i in 0..len {
+        let t: f64 = start + i as f64 * interval_s;
+        while next < values.len() && values[next].0 <= t {
+            current = values[next].1;
+            next += 1;
+        }
+        out.push((t, current));
+    }
+    out
+}